@@ -0,0 +1,206 @@
+use crate::list::KeyNodeList;
+use crate::map::{Map, MapLookup};
+use crate::node::Node;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::Hash;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// A [`KeyNodeList`] with a maximum length.
+///
+/// When the length of the list exceeds the maximum length after pushing
+/// a key-node pair to one end of the list, the pair at the other end will
+/// be evicted.
+///
+/// `BoundedList` dereferences to [`KeyNodeList`], so all read-only
+/// operations of [`KeyNodeList`] are also available.
+#[derive(Clone)]
+pub struct BoundedList<
+  K,
+  N,
+  #[cfg(feature = "std")] M = HashMap<K, N>,
+  #[cfg(not(feature = "std"))] M,
+> {
+  list: KeyNodeList<K, N, M>,
+  max_len: usize,
+}
+
+impl<K, N, M> BoundedList<K, N, M>
+where
+  M: Default,
+{
+  /// Creates an empty bounded list with the given maximum length.
+  #[inline]
+  pub fn new(max_len: usize) -> Self {
+    Self {
+      list: KeyNodeList::new(),
+      max_len,
+    }
+  }
+}
+
+impl<K, N, M> BoundedList<K, N, M>
+where
+  M: Map<K, N>,
+{
+  /// Creates an empty bounded list with the given hash map `map` and
+  /// maximum length.
+  ///
+  /// The given hash map should be empty.
+  #[inline]
+  pub fn with_map(map: M, max_len: usize) -> Self {
+    Self {
+      list: KeyNodeList::with_map(map),
+      max_len,
+    }
+  }
+
+  /// Returns the maximum length of the list.
+  #[inline]
+  pub fn max_len(&self) -> usize {
+    self.max_len
+  }
+
+  /// Consumes this [`BoundedList`], returning the underlying [`KeyNodeList`].
+  #[inline]
+  pub fn into_inner(self) -> KeyNodeList<K, N, M> {
+    self.list
+  }
+
+  /// Removes all key-node pairs in the list.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.list.clear()
+  }
+}
+
+impl<K, N, M> BoundedList<K, N, M>
+where
+  K: Hash + Eq,
+  M: Map<K, N>,
+{
+  /// Returns a mutable reference to the node corresponding to the key,
+  /// or `None` if key does not exist.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn node_mut<Q>(&mut self, key: &Q) -> Option<&mut N>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.list.node_mut(key)
+  }
+}
+
+impl<K, N, M> BoundedList<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Sets the maximum length of the list.
+  ///
+  /// If the list is longer than `max_len`, pairs at the front of the list
+  /// are evicted immediately, and returned in list order.
+  pub fn set_max_len(&mut self, max_len: usize) -> Vec<(K, N)> {
+    self.max_len = max_len;
+    let count = self.list.len().saturating_sub(max_len);
+    (0..count).filter_map(|_| self.list.pop_front()).collect()
+  }
+
+  /// Adds a key-node pair first in the list.
+  ///
+  /// If the length of the list exceeds the maximum length, the back pair
+  /// is evicted and returned.
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`,
+  /// and nothing is evicted.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn push_front<T: Into<N>>(&mut self, key: K, node: T) -> Result<Option<(K, N)>, (K, T)> {
+    self.list.push_front(key, node).map(|_| {
+      if self.list.len() > self.max_len {
+        self.list.pop_back()
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Adds a key-node pair back in the list.
+  ///
+  /// If the length of the list exceeds the maximum length, the front pair
+  /// is evicted and returned.
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`,
+  /// and nothing is evicted.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn push_back<T: Into<N>>(&mut self, key: K, node: T) -> Result<Option<(K, N)>, (K, T)> {
+    self.list.push_back(key, node).map(|_| {
+      if self.list.len() > self.max_len {
+        self.list.pop_front()
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Removes the first key-node pair and returns it, or `None` if the list
+  /// is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn pop_front(&mut self) -> Option<(K, N)> {
+    self.list.pop_front()
+  }
+
+  /// Removes the last key-node pair and returns it, or `None` if the list
+  /// is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn pop_back(&mut self) -> Option<(K, N)> {
+    self.list.pop_back()
+  }
+
+  /// Removes the key-node pair at the given key and returns it,
+  /// or returns `None` if `key` does not exists.
+  #[inline]
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, N)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.list.remove(key)
+  }
+}
+
+impl<K, N, M> Deref for BoundedList<K, N, M> {
+  type Target = KeyNodeList<K, N, M>;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.list
+  }
+}
+
+impl<K, N, M> fmt::Debug for BoundedList<K, N, M>
+where
+  K: Hash + Eq + fmt::Debug,
+  N: Node<Key = K> + fmt::Debug,
+  M: Map<K, N>,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("BoundedList")
+      .field("list", &self.list)
+      .field("max_len", &self.max_len)
+      .finish()
+  }
+}
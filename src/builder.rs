@@ -0,0 +1,106 @@
+use crate::list::KeyNodeList;
+use crate::map::Map;
+use crate::node::Node;
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// A builder for constructing a [`KeyNodeList`] by chaining pushes.
+///
+/// Each push fails immediately if the key already exists, so the offending
+/// push can be located easily.
+///
+/// # Examples
+///
+/// ```
+/// use key_node_list::{BTreeValueList, KeyNodeListBuilder};
+///
+/// let mut builder = KeyNodeListBuilder::new();
+/// builder.push(1, "a").unwrap().push(2, "b").unwrap();
+/// assert_eq!(builder.push(1, "c").err(), Some((1, "c")));
+/// let list: BTreeValueList<_, _> = builder.build();
+/// assert_eq!(list.len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct KeyNodeListBuilder<
+  K,
+  N,
+  #[cfg(feature = "std")] M = HashMap<K, N>,
+  #[cfg(not(feature = "std"))] M,
+> {
+  list: KeyNodeList<K, N, M>,
+}
+
+impl<K, N, M> KeyNodeListBuilder<K, N, M>
+where
+  M: Default,
+{
+  /// Creates a new builder with an empty list.
+  #[inline]
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl<K, N, M> KeyNodeListBuilder<K, N, M>
+where
+  M: Map<K, N>,
+{
+  /// Creates a new builder with the given hash map `map`.
+  ///
+  /// The given hash map should be empty.
+  #[inline]
+  pub fn with_map(map: M) -> Self {
+    Self {
+      list: KeyNodeList::with_map(map),
+    }
+  }
+
+  /// Returns the number of key-node pairs pushed so far.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.list.len()
+  }
+
+  /// Returns `true` if no key-node pairs have been pushed.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.list.is_empty()
+  }
+
+  /// Consumes the builder and returns the built list.
+  #[inline]
+  pub fn build(self) -> KeyNodeList<K, N, M> {
+    self.list
+  }
+}
+
+impl<K, N, M> KeyNodeListBuilder<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Pushes a key-node pair to the back of the list being built,
+  /// and returns the builder for chaining.
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn push<T: Into<N>>(&mut self, key: K, node: T) -> Result<&mut Self, (K, T)> {
+    self.list.push_back(key, node).map(|_| self)
+  }
+}
+
+impl<K, N, M> Default for KeyNodeListBuilder<K, N, M>
+where
+  M: Default,
+{
+  #[inline]
+  fn default() -> Self {
+    Self {
+      list: KeyNodeList::default(),
+    }
+  }
+}
@@ -0,0 +1,186 @@
+use crate::node::ValueNode;
+use crate::KeyValueList;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::Hash;
+
+/// A least-recently-used cache built on [`KeyValueList`].
+///
+/// Key-value pairs are kept in recency order: the front of the list is the
+/// least recently used pair, and the back is the most recently used one.
+/// Accessing a pair moves it to the back without touching the underlying
+/// hash map, and inserting a pair into a full cache evicts the front pair.
+#[derive(Clone)]
+pub struct LruCache<K, V> {
+  list: KeyValueList<K, V>,
+  capacity: usize,
+}
+
+impl<K, V> LruCache<K, V> {
+  /// Creates an empty cache that holds at most `capacity` key-value pairs.
+  #[inline]
+  pub fn new(capacity: usize) -> Self {
+    Self {
+      list: KeyValueList::new(),
+      capacity,
+    }
+  }
+
+  /// Returns the maximum number of key-value pairs in the cache.
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  /// Returns the number of key-value pairs in the cache.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.list.len()
+  }
+
+  /// Returns `true` if the cache contains no key-value pairs.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.list.is_empty()
+  }
+
+  /// Removes all key-value pairs in the cache.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.list.clear()
+  }
+
+  /// Returns a reference to the underlying [`KeyValueList`], in which the
+  /// pairs are ordered from the least recently used to the most recently
+  /// used.
+  #[inline]
+  pub fn as_list(&self) -> &KeyValueList<K, V> {
+    &self.list
+  }
+
+  /// Consumes this [`LruCache`], returning the underlying [`KeyValueList`].
+  #[inline]
+  pub fn into_inner(self) -> KeyValueList<K, V> {
+    self.list
+  }
+}
+
+impl<K, V> LruCache<K, V>
+where
+  K: Hash + Eq + Clone,
+{
+  /// Returns `true` if the cache contains a value for the specified key,
+  /// without updating the recency of the pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.list.contains_key(key)
+  }
+
+  /// Returns a reference to the value corresponding to the key, and marks
+  /// the pair as the most recently used one.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    if self.list.move_to_back(key) {
+      self.list.node(key).map(ValueNode::value)
+    } else {
+      None
+    }
+  }
+
+  /// Returns a mutable reference to the value corresponding to the key,
+  /// and marks the pair as the most recently used one.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    if self.list.move_to_back(key) {
+      self.list.node_mut(key).map(ValueNode::value_mut)
+    } else {
+      None
+    }
+  }
+
+  /// Returns a reference to the value corresponding to the key, without
+  /// updating the recency of the pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.list.node(key).map(ValueNode::value)
+  }
+
+  /// Inserts a key-value pair into the cache, and marks the pair as the
+  /// most recently used one.
+  ///
+  /// If `key` already exists, its value is replaced by `value`, and nothing
+  /// is evicted. Otherwise, if the number of pairs exceeds the capacity,
+  /// the least recently used pair is evicted and returned.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+    if let Some(node) = self.list.node_mut(&key) {
+      *node.value_mut() = value;
+      self.list.move_to_back(&key);
+      return None;
+    }
+    self.list.push_back_unchecked(key, value);
+    if self.list.len() > self.capacity {
+      self.pop_lru()
+    } else {
+      None
+    }
+  }
+
+  /// Removes the least recently used key-value pair and returns it,
+  /// or `None` if the cache is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn pop_lru(&mut self) -> Option<(K, V)> {
+    self.list.pop_front().map(|(k, n)| (k, n.into_value()))
+  }
+
+  /// Removes the key-value pair at the given key and returns it,
+  /// or returns `None` if `key` does not exists.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.list.remove(key).map(|(k, n)| (k, n.into_value()))
+  }
+}
+
+impl<K, V> fmt::Debug for LruCache<K, V>
+where
+  K: Hash + Eq + fmt::Debug,
+  V: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("LruCache")
+      .field("list", &self.list)
+      .field("capacity", &self.capacity)
+      .finish()
+  }
+}
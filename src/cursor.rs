@@ -1,9 +1,15 @@
-use crate::list::KeyNodeList;
+use crate::list::{KeyNodeList, Op};
 use crate::map::Map;
 use crate::node::Node;
 use crate::{node_next_mut, node_prev_mut};
-use std::fmt;
-use std::hash::Hash;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
 
 macro_rules! impl_cursor {
   ($name:ident<$a:lifetime, $k:ident, $n:ident, $m:ident>($list:ident, $key:ident)) => {
@@ -116,6 +122,40 @@ macro_rules! impl_cursor {
       pub fn prev_node(&self) -> Option<&$n> {
         self.prev_key().and_then(|k| self.$list.node(k))
       }
+
+      /// Returns up to `n` key-node pairs starting from the current pair
+      /// (inclusive) and following the next keys, without moving the cursor.
+      ///
+      /// Returns an empty vector if the cursor is pointing to the null pair.
+      ///
+      /// This operation should compute in *O*(n) time on average.
+      pub fn lookahead(&self, n: usize) -> Vec<(&$k, &$n)> {
+        let mut pair = self.$key.as_ref().and_then(|k| self.$list.get_key_node(k));
+        iter::from_fn(|| {
+          let (k, node) = pair?;
+          pair = node.next().and_then(|k| self.$list.get_key_node::<$k>(k));
+          Some((k, node))
+        })
+        .take(n)
+        .collect()
+      }
+
+      /// Returns up to `n` key-node pairs starting from the current pair
+      /// (inclusive) and following the previous keys, without moving the cursor.
+      ///
+      /// Returns an empty vector if the cursor is pointing to the null pair.
+      ///
+      /// This operation should compute in *O*(n) time on average.
+      pub fn lookbehind(&self, n: usize) -> Vec<(&$k, &$n)> {
+        let mut pair = self.$key.as_ref().and_then(|k| self.$list.get_key_node(k));
+        iter::from_fn(|| {
+          let (k, node) = pair?;
+          pair = node.prev().and_then(|k| self.$list.get_key_node::<$k>(k));
+          Some((k, node))
+        })
+        .take(n)
+        .collect()
+      }
     }
 
     impl<$a, $k, $n, $m> $name<$a, $k, $n, $m>
@@ -132,7 +172,7 @@ macro_rules! impl_cursor {
       /// to the null pair.
       #[inline]
       pub fn move_next(&mut self) {
-        self.$key = self.$key.as_ref().map_or_else(
+        *self.key_mut() = self.$key.as_ref().map_or_else(
           || self.$list.head.clone(),
           |k| self.$list.node(k).and_then(|n| n.next().cloned()),
         );
@@ -146,11 +186,100 @@ macro_rules! impl_cursor {
       /// to the null pair.
       #[inline]
       pub fn move_prev(&mut self) {
-        self.$key = self.$key.as_ref().map_or_else(
+        *self.key_mut() = self.$key.as_ref().map_or_else(
           || self.$list.tail.clone(),
           |k| self.$list.node(k).and_then(|n| n.prev().cloned()),
         );
       }
+
+      /// Moves the cursor to the next key-node pair up to `n` times, and
+      /// returns clones of the keys of the pairs the cursor landed on,
+      /// excluding the starting pair.
+      ///
+      /// Stops if the cursor reaches the null pair.
+      ///
+      /// This operation should compute in *O*(n) time on average.
+      pub fn advance_collecting(&mut self, n: usize) -> Vec<$k> {
+        let mut keys = Vec::new();
+        for _ in 0..n {
+          self.move_next();
+          match self.$key.as_ref() {
+            Some(k) => keys.push(k.clone()),
+            None => break,
+          }
+        }
+        keys
+      }
+
+      /// Moves the cursor to the previous key-node pair up to `n` times, and
+      /// returns clones of the keys of the pairs the cursor landed on,
+      /// excluding the starting pair.
+      ///
+      /// Stops if the cursor reaches the null pair.
+      ///
+      /// This operation should compute in *O*(n) time on average.
+      pub fn retreat_collecting(&mut self, n: usize) -> Vec<$k> {
+        let mut keys = Vec::new();
+        for _ in 0..n {
+          self.move_prev();
+          match self.$key.as_ref() {
+            Some(k) => keys.push(k.clone()),
+            None => break,
+          }
+        }
+        keys
+      }
+
+      /// Moves the cursor toward the back of the [`KeyNodeList`] until it
+      /// points to `key`, moving at most `max_steps` times.
+      ///
+      /// The current pair is checked first. Returns `Some(true)` if `key` is
+      /// found, or `Some(false)` if the cursor reaches the null pair.
+      /// Otherwise returns `None` when the step budget runs out, and the
+      /// cursor is left where it stopped, so the search can be resumed.
+      ///
+      /// This operation should compute in *O*(max_steps) time on average.
+      pub fn seek_forward_bounded<Q>(&mut self, key: &Q, max_steps: usize) -> Option<bool>
+      where
+        $k: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+      {
+        let mut steps = 0;
+        loop {
+          match self.$key.as_ref() {
+            Some(k) if k.borrow() == key => return Some(true),
+            Some(_) => {}
+            None => return Some(false),
+          }
+          if steps == max_steps {
+            return None;
+          }
+          self.move_next();
+          steps += 1;
+        }
+      }
+
+      /// Returns a clone of the next key.
+      ///
+      /// This is the same as [`next_key`](Self::next_key), but the returned key
+      /// does not borrow the cursor, so the list can be mutated afterwards.
+      ///
+      /// This operation should compute in *O*(1) time on average.
+      #[inline]
+      pub fn next_key_cloned(&self) -> Option<$k> {
+        self.next_key().cloned()
+      }
+
+      /// Returns a clone of the previous key.
+      ///
+      /// This is the same as [`prev_key`](Self::prev_key), but the returned key
+      /// does not borrow the cursor, so the list can be mutated afterwards.
+      ///
+      /// This operation should compute in *O*(1) time on average.
+      #[inline]
+      pub fn prev_key_cloned(&self) -> Option<$k> {
+        self.prev_key().cloned()
+      }
     }
 
     impl<$a, $k, $n, $m> fmt::Debug for $name<$a, $k, $n, $m>
@@ -162,7 +291,7 @@ macro_rules! impl_cursor {
       fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple(stringify!($name))
           .field(self.$list)
-          .field(&self.$key)
+          .field(&self.$key.as_ref())
           .finish()
       }
     }
@@ -178,14 +307,115 @@ pub struct Cursor<'a, K, N, M> {
 
 impl_cursor!(Cursor<'a, K, N, M>(list, key));
 
+impl<'a, K, N, M> Cursor<'a, K, N, M> {
+  #[inline]
+  fn key_mut(&mut self) -> &mut Option<K> {
+    &mut self.key
+  }
+
+  /// Returns a reference to the key that identifies the position of the
+  /// cursor in its parent list, or `None` for the null pair.
+  ///
+  /// Together with the address of the parent list, this is what
+  /// [`PartialEq`] and [`Hash`] of [`Cursor`] are based on.
+  #[inline]
+  pub fn position_key(&self) -> Option<&K> {
+    self.key.as_ref()
+  }
+}
+
+/// Two cursors are equal if they point to the same pair of the same list
+/// instance. Cursors into different list instances are never equal,
+/// even if the contents of the lists are equal.
+impl<'a, K, N, M> PartialEq for Cursor<'a, K, N, M>
+where
+  K: PartialEq,
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    ptr::eq(self.list, other.list) && self.key == other.key
+  }
+}
+
+impl<'a, K, N, M> Eq for Cursor<'a, K, N, M> where K: Eq {}
+
+impl<'a, K, N, M> Hash for Cursor<'a, K, N, M>
+where
+  K: Hash,
+{
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    ptr::hash(self.list, state);
+    self.key.hash(state);
+  }
+}
+
 /// A cursor over a [`KeyNodeList`] with editing operations.
 pub struct CursorMut<'a, K, N, M> {
   pub(crate) list: &'a mut KeyNodeList<K, N, M>,
-  pub(crate) key: Option<K>,
+  pub(crate) key: KeySlot<'a, K>,
 }
 
 impl_cursor!(CursorMut<'a, K, N, M>(list, key));
 
+/// Storage of the position of a [`CursorMut`].
+///
+/// A reborrowed cursor shares the slot of its parent, so that the parent
+/// never keeps a key that was removed through the reborrowed cursor.
+pub(crate) enum KeySlot<'a, K> {
+  Owned(Option<K>),
+  Shared(&'a mut Option<K>),
+}
+
+impl<'a, K> From<Option<K>> for KeySlot<'a, K> {
+  #[inline]
+  fn from(key: Option<K>) -> Self {
+    Self::Owned(key)
+  }
+}
+
+impl<'a, K> Deref for KeySlot<'a, K> {
+  type Target = Option<K>;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    match self {
+      Self::Owned(key) => key,
+      Self::Shared(key) => key,
+    }
+  }
+}
+
+impl<'a, K> DerefMut for KeySlot<'a, K> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    match self {
+      Self::Owned(key) => key,
+      Self::Shared(key) => key,
+    }
+  }
+}
+
+impl<'a, K, N, M> CursorMut<'a, K, N, M> {
+  #[inline]
+  fn key_mut(&mut self) -> &mut Option<K> {
+    &mut self.key
+  }
+}
+
+/// The position where a key-node pair was inserted by a [`CursorMut`].
+///
+/// Returned by [`CursorMut::insert_after_reporting`] and
+/// [`CursorMut::insert_before_reporting`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InsertReport<K> {
+  /// Key of the pair that precedes the inserted pair,
+  /// `None` if the inserted pair is at the front of the list.
+  pub prev: Option<K>,
+  /// Key of the pair that follows the inserted pair,
+  /// `None` if the inserted pair is at the back of the list.
+  pub next: Option<K>,
+}
+
 impl<'a, K, N, M> CursorMut<'a, K, N, M>
 where
   K: Clone,
@@ -196,12 +426,27 @@ where
   /// [`CursorMut`], which means it cannot outlive the [`CursorMut`] and that
   /// the [`CursorMut`] is frozen for the lifetime of the [`Cursor`].
   #[inline]
-  pub fn as_cursor(&self) -> Cursor<K, N, M> {
+  pub fn as_cursor(&self) -> Cursor<'_, K, N, M> {
     Cursor {
       list: self.list,
       key: self.key.clone(),
     }
   }
+
+  /// Returns a mutable cursor pointing to the current pair, which has
+  /// a shorter lifetime than this [`CursorMut`].
+  ///
+  /// The [`CursorMut`] is frozen for the lifetime of the returned cursor,
+  /// and shares its position with the returned cursor. Moving the returned
+  /// cursor, or removing pairs through it, also moves this [`CursorMut`],
+  /// so it never points to a pair that is no longer in the list.
+  #[inline]
+  pub fn reborrow(&mut self) -> CursorMut<'_, K, N, M> {
+    CursorMut {
+      list: self.list,
+      key: KeySlot::Shared(&mut self.key),
+    }
+  }
 }
 
 impl<'a, K, N, M> CursorMut<'a, K, N, M>
@@ -256,7 +501,7 @@ where
   pub fn insert_after<T: Into<N>>(&mut self, key: K, node: T) -> Result<(), (K, T)> {
     self.list.nodes.insert(key.clone(), node).map(|_| {
       // get the `next` pointer of the node pointed by the cursor
-      let next = match &self.key {
+      let next = match &*self.key {
         // cursor points to the key `k`
         // update the `next` pointer of the `k` node
         Some(k) => node_next_mut!(self.list, k).replace(key.clone()),
@@ -275,6 +520,7 @@ where
       let node = self.list.node_mut(&key).unwrap();
       *node_prev_mut!(node) = self.key.clone();
       *node_next_mut!(node) = next;
+      self.list.record_op(Op::Insert);
     })
   }
 
@@ -287,7 +533,7 @@ where
   pub fn insert_before<T: Into<N>>(&mut self, key: K, node: T) -> Result<(), (K, T)> {
     self.list.nodes.insert(key.clone(), node).map(|_| {
       // get the `prev` pointer of the node pointed by the cursor
-      let prev = match &self.key {
+      let prev = match &*self.key {
         // cursor points to the key `k`
         // update the `prev` pointer of the `k` node
         Some(k) => node_prev_mut!(self.list, k).replace(key.clone()),
@@ -306,9 +552,56 @@ where
       let node = self.list.node_mut(&key).unwrap();
       *node_prev_mut!(node) = prev;
       *node_next_mut!(node) = self.key.clone();
+      self.list.record_op(Op::Insert);
     })
   }
 
+  /// Inserts a new key-node pair into the [`KeyNodeList`] after the current one,
+  /// and returns the keys of the pairs that now precede and follow the
+  /// inserted pair.
+  ///
+  /// If the cursor is pointing at the null pair then the new pair is inserted
+  /// at the front of the [`KeyNodeList`].
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`.
+  pub fn insert_after_reporting<T: Into<N>>(
+    &mut self,
+    key: K,
+    node: T,
+  ) -> Result<InsertReport<K>, (K, T)> {
+    let report = InsertReport {
+      prev: self.key.clone(),
+      next: match &*self.key {
+        Some(k) => self.list.node(k).and_then(|n| n.next().cloned()),
+        None => self.list.head.clone(),
+      },
+    };
+    self.insert_after(key, node).map(|_| report)
+  }
+
+  /// Inserts a new key-node pair into the [`KeyNodeList`] before the current one,
+  /// and returns the keys of the pairs that now precede and follow the
+  /// inserted pair.
+  ///
+  /// If the cursor is pointing at the null pair then the new pair is inserted
+  /// at the end of the [`KeyNodeList`].
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`.
+  pub fn insert_before_reporting<T: Into<N>>(
+    &mut self,
+    key: K,
+    node: T,
+  ) -> Result<InsertReport<K>, (K, T)> {
+    let report = InsertReport {
+      prev: match &*self.key {
+        Some(k) => self.list.node(k).and_then(|n| n.prev().cloned()),
+        None => self.list.tail.clone(),
+      },
+      next: self.key.clone(),
+    };
+    self.insert_before(key, node).map(|_| report)
+  }
+
   /// Inserts a key into the [`KeyNodeList`] after the current one.
   ///
   /// If the cursor is pointing at the null pair then the key is inserted
@@ -335,6 +628,25 @@ where
     self.insert_before(key, ()).map_err(|(k, _)| k)
   }
 
+  /// Replaces the node that the cursor is currently pointing to with `node`,
+  /// and returns the old node.
+  ///
+  /// The previous and next pointers of the old node are copied to the new
+  /// node, so the position of the current pair is unchanged.
+  ///
+  /// If the cursor is currently pointing to the null pair then nothing is
+  /// replaced and `None` is returned.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn replace_current<T: Into<N>>(&mut self, node: T) -> Option<N> {
+    let mut node = node.into();
+    self.node_mut().map(|old| {
+      *node_prev_mut!(node) = old.prev().cloned();
+      *node_next_mut!(node) = old.next().cloned();
+      mem::replace(old, node)
+    })
+  }
+
   /// Removes the current pair from the [`KeyNodeList`].
   ///
   /// The pair that was removed is returned, and the cursor is moved to point
@@ -346,11 +658,73 @@ where
   pub fn remove_current(&mut self) -> Option<(K, N)> {
     self.key.take().map(|k| {
       let pair = self.list.remove(&k).unwrap();
-      self.key = pair.1.next().cloned();
+      *self.key = pair.1.next().cloned();
       pair
     })
   }
 
+  /// Converts the cursor into an iterator that removes and yields the
+  /// current pair, and then moves to the next pair, until the null pair
+  /// is reached.
+  ///
+  /// Each pair is removed only when it is yielded, so dropping the iterator
+  /// early leaves the remaining pairs in the list.
+  #[inline]
+  pub fn into_consuming_iter(mut self) -> impl Iterator<Item = (K, N)> + 'a {
+    iter::from_fn(move || self.remove_current())
+  }
+
+  /// Removes all pairs from the current one up to (but not including) the
+  /// pair at `end_key`, and returns them as a new list in order.
+  ///
+  /// The cursor is left pointing to `end_key`. If `end_key` can not be
+  /// reached by moving forward, all pairs through the back of the list are
+  /// removed, and the cursor is left pointing to the null pair. If the
+  /// cursor is pointing to the null pair then nothing is removed.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the number of removed pairs.
+  pub fn remove_until<Q>(&mut self, end_key: &Q) -> KeyNodeList<K, N, M>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: Default,
+  {
+    let mut removed = KeyNodeList::new();
+    while self.key.as_ref().is_some_and(|k| k.borrow() != end_key) {
+      let (key, node) = self.remove_current().unwrap();
+      removed.push_back_unchecked(key, node);
+    }
+    removed
+  }
+
+  /// Moves the current pair to the back of the [`KeyNodeList`], and moves
+  /// the cursor to the next pair of the moved pair.
+  ///
+  /// The pair is relinked without being removed from the hash map. If the
+  /// current pair is already at the back of the list, the list is unchanged
+  /// and the cursor is moved to the null pair. If the cursor is pointing to
+  /// the null pair then nothing happens.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn rotate_current_to_back(&mut self) {
+    let key = match &*self.key {
+      Some(k) => k.clone(),
+      None => return,
+    };
+    if self.list.tail.as_ref() == Some(&key) {
+      *self.key = None;
+      return;
+    }
+    // the current node is not the back node, so its next node always exists
+    let next = self.list.node(&key).and_then(|n| n.next().cloned());
+    self.list.unlink(&key);
+    let tail = self.list.tail.clone();
+    self.list.link_between(&key, tail, None);
+    self.list.record_op(Op::Move);
+    *self.key = next;
+  }
+
   /// Appends an pair to the front of the cursor’s parent list. The pair that
   /// the cursor points to is unchanged, even if it is the null pair.
   ///
@@ -381,7 +755,7 @@ where
   /// This operation should compute in *O*(1) time on average.
   #[inline]
   pub fn pop_front(&mut self) -> Option<(K, N)> {
-    if self.list.head == self.key {
+    if self.list.head == *self.key {
       self.move_next();
     }
     self.list.pop_front()
@@ -395,8 +769,8 @@ where
   /// This operation should compute in *O*(1) time on average.
   #[inline]
   pub fn pop_back(&mut self) -> Option<(K, N)> {
-    if self.list.tail == self.key {
-      self.key = None;
+    if self.list.tail == *self.key {
+      *self.key = None;
     }
     self.list.pop_back()
   }
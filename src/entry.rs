@@ -0,0 +1,185 @@
+use crate::list::KeyNodeList;
+use crate::map::Map;
+use crate::node::Node;
+use core::hash::Hash;
+
+/// A view into a single key of a [`KeyNodeList`], which may either be
+/// occupied or vacant, created by [`KeyNodeList::entry`].
+///
+/// Unlike the entries of a hash map, inserting into a vacant entry must
+/// specify the position of the new pair, which is the front or the back
+/// of the list.
+pub enum Entry<'a, K, N, M> {
+  /// An occupied entry.
+  Occupied(OccupiedEntry<'a, K, N, M>),
+  /// A vacant entry.
+  Vacant(VacantEntry<'a, K, N, M>),
+}
+
+/// A view into an occupied entry of a [`KeyNodeList`].
+pub struct OccupiedEntry<'a, K, N, M> {
+  list: &'a mut KeyNodeList<K, N, M>,
+  key: K,
+}
+
+/// A view into a vacant entry of a [`KeyNodeList`].
+pub struct VacantEntry<'a, K, N, M> {
+  list: &'a mut KeyNodeList<K, N, M>,
+  key: K,
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Gets the entry of the given key for in-place manipulation.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn entry(&mut self, key: K) -> Entry<'_, K, N, M> {
+    if self.contains_key(&key) {
+      Entry::Occupied(OccupiedEntry { list: self, key })
+    } else {
+      Entry::Vacant(VacantEntry { list: self, key })
+    }
+  }
+}
+
+impl<'a, K, N, M> Entry<'a, K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Returns a reference to the key of the entry.
+  #[inline]
+  pub fn key(&self) -> &K {
+    match self {
+      Self::Occupied(e) => e.key(),
+      Self::Vacant(e) => e.key(),
+    }
+  }
+
+  /// Returns a mutable reference to the node of the entry. If the entry is
+  /// vacant, `default` is inserted at the back of the list first.
+  #[inline]
+  pub fn or_insert_back<T: Into<N>>(self, default: T) -> &'a mut N {
+    match self {
+      Self::Occupied(e) => e.into_mut(),
+      Self::Vacant(e) => e.insert_back(default),
+    }
+  }
+
+  /// Returns a mutable reference to the node of the entry. If the entry is
+  /// vacant, `default` is inserted at the front of the list first.
+  #[inline]
+  pub fn or_insert_front<T: Into<N>>(self, default: T) -> &'a mut N {
+    match self {
+      Self::Occupied(e) => e.into_mut(),
+      Self::Vacant(e) => e.insert_front(default),
+    }
+  }
+
+  /// Calls `f` with a mutable reference to the node if the entry is
+  /// occupied, and returns the entry.
+  #[inline]
+  pub fn and_modify<F>(mut self, f: F) -> Self
+  where
+    F: FnOnce(&mut N),
+  {
+    if let Self::Occupied(e) = &mut self {
+      f(e.get_mut());
+    }
+    self
+  }
+}
+
+impl<'a, K, N, M> OccupiedEntry<'a, K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Returns a reference to the key of the entry.
+  #[inline]
+  pub fn key(&self) -> &K {
+    &self.key
+  }
+
+  /// Returns a reference to the node of the entry.
+  #[inline]
+  pub fn get(&self) -> &N {
+    self.list.node(&self.key).unwrap()
+  }
+
+  /// Returns a mutable reference to the node of the entry.
+  #[inline]
+  pub fn get_mut(&mut self) -> &mut N {
+    self.list.node_mut(&self.key).unwrap()
+  }
+
+  /// Converts the entry into a mutable reference to the node, with the
+  /// lifetime of the list.
+  #[inline]
+  pub fn into_mut(self) -> &'a mut N {
+    self.list.node_mut(&self.key).unwrap()
+  }
+
+  /// Removes the key-node pair of the entry from the list, links its
+  /// previous and next pairs, and returns the removed pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn remove(self) -> (K, N) {
+    self.list.remove(&self.key).unwrap()
+  }
+}
+
+impl<'a, K, N, M> VacantEntry<'a, K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Returns a reference to the key of the entry.
+  #[inline]
+  pub fn key(&self) -> &K {
+    &self.key
+  }
+
+  /// Takes the ownership of the key.
+  #[inline]
+  pub fn into_key(self) -> K {
+    self.key
+  }
+
+  /// Inserts the key and `node` at the back of the list, and returns a
+  /// mutable reference to the node.
+  ///
+  /// Unlike a hash map entry, the entry does not keep the slot it found, so
+  /// inserting looks the key up again: once to insert the pair, once to
+  /// link it, and once more to return the reference. The old back pair
+  /// of the list is also looked up to link it to the new pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn insert_back<T: Into<N>>(self, node: T) -> &'a mut N {
+    self.list.push_back_unchecked(self.key.clone(), node);
+    self.list.node_mut(&self.key).unwrap()
+  }
+
+  /// Inserts the key and `node` at the front of the list, and returns a
+  /// mutable reference to the node.
+  ///
+  /// Unlike a hash map entry, the entry does not keep the slot it found, so
+  /// inserting looks the key up again: once to insert the pair, once to
+  /// link it, and once more to return the reference. The old front pair
+  /// of the list is also looked up to link it to the new pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn insert_front<T: Into<N>>(self, node: T) -> &'a mut N {
+    self.list.push_front_unchecked(self.key.clone(), node);
+    self.list.node_mut(&self.key).unwrap()
+  }
+}
@@ -0,0 +1,237 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+/// An error returned by [`KeyNodeList::try_index`](crate::KeyNodeList::try_index)
+/// when the given key does not exist in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MissingKey<K>(pub K);
+
+impl<K> MissingKey<K> {
+  /// Returns the missing key.
+  #[inline]
+  pub fn into_key(self) -> K {
+    self.0
+  }
+}
+
+impl<K: fmt::Debug> fmt::Display for MissingKey<K> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "no entry found for key {:?}", self.0)
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug> std::error::Error for MissingKey<K> {}
+
+/// An error returned by
+/// [`KeyNodeList::from_entries_in_order`](crate::KeyNodeList::from_entries_in_order)
+/// when the given key appears more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DuplicateKey<K>(pub K);
+
+impl<K> DuplicateKey<K> {
+  /// Returns the duplicate key.
+  #[inline]
+  pub fn into_key(self) -> K {
+    self.0
+  }
+}
+
+impl<K: fmt::Debug> fmt::Display for DuplicateKey<K> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "duplicate key {:?}", self.0)
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug> std::error::Error for DuplicateKey<K> {}
+
+/// An error returned by [`KeyNodeList::append`](crate::KeyNodeList::append)
+/// when some keys exist in both lists.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCollision<K>(pub Vec<K>);
+
+impl<K> KeyCollision<K> {
+  /// Returns the colliding keys.
+  #[inline]
+  pub fn into_keys(self) -> Vec<K> {
+    self.0
+  }
+}
+
+impl<K: fmt::Debug> fmt::Display for KeyCollision<K> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "keys {:?} exist in both lists", self.0)
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug> std::error::Error for KeyCollision<K> {}
+
+/// An error returned by [`KeyNodeList::extend_try`](crate::KeyNodeList::extend_try).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtendError<K, N, E> {
+  /// Failed to convert the item at `index` into a node.
+  Convert {
+    /// Index of the item in the iterator.
+    index: usize,
+    /// Key of the item.
+    key: K,
+    /// The conversion error.
+    error: E,
+  },
+  /// The key of the item at `index` already exists in the list.
+  DuplicateKey {
+    /// Index of the item in the iterator.
+    index: usize,
+    /// The duplicate key.
+    key: K,
+    /// The converted node.
+    node: N,
+  },
+}
+
+impl<K, N, E> ExtendError<K, N, E> {
+  /// Returns the index of the item that caused the error.
+  #[inline]
+  pub fn index(&self) -> usize {
+    match self {
+      Self::Convert { index, .. } | Self::DuplicateKey { index, .. } => *index,
+    }
+  }
+
+  /// Returns a reference to the key of the item that caused the error.
+  #[inline]
+  pub fn key(&self) -> &K {
+    match self {
+      Self::Convert { key, .. } | Self::DuplicateKey { key, .. } => key,
+    }
+  }
+}
+
+impl<K: fmt::Debug, N, E: fmt::Display> fmt::Display for ExtendError<K, N, E> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Convert { index, key, error } => write!(
+        f,
+        "failed to convert item {} with key {:?}: {}",
+        index, key, error
+      ),
+      Self::DuplicateKey { index, key, .. } => {
+        write!(f, "duplicate key {:?} at item {}", key, index)
+      }
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K, N, E> std::error::Error for ExtendError<K, N, E>
+where
+  K: fmt::Debug,
+  N: fmt::Debug,
+  E: fmt::Debug + fmt::Display,
+{
+}
+
+/// An error returned by [`KeyNodeList::reorder`](crate::KeyNodeList::reorder)
+/// when the given order is not a permutation of the keys in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReorderError {
+  /// The length of the order does not match the length of the list.
+  LengthMismatch {
+    /// Length of the list.
+    expected: usize,
+    /// Length of the order.
+    found: usize,
+  },
+  /// The key at the index of the order does not exist in the list.
+  UnknownKey(usize),
+  /// The key at the index of the order appears more than once.
+  DuplicateKey(usize),
+}
+
+impl fmt::Display for ReorderError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::LengthMismatch { expected, found } => write!(
+        f,
+        "order length mismatch, expected {}, found {}",
+        expected, found
+      ),
+      Self::UnknownKey(index) => write!(f, "unknown key at index {} of the order", index),
+      Self::DuplicateKey(index) => {
+        write!(f, "duplicate key at index {} of the order", index)
+      }
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReorderError {}
+
+/// An error returned by
+/// [`KeyNodeList::insert_all_after_key`](crate::KeyNodeList::insert_all_after_key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InsertError<K, T> {
+  /// The anchor key does not exist in the list.
+  MissingAnchor,
+  /// The key of the item at `index` already exists in the list.
+  DuplicateKey {
+    /// Index of the item in the iterator.
+    index: usize,
+    /// The duplicate key.
+    key: K,
+    /// The item that was not inserted.
+    node: T,
+  },
+}
+
+impl<K: fmt::Debug, T> fmt::Display for InsertError<K, T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::MissingAnchor => write!(f, "anchor key not found"),
+      Self::DuplicateKey { index, key, .. } => {
+        write!(f, "duplicate key {:?} at item {}", key, index)
+      }
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug, T: fmt::Debug> std::error::Error for InsertError<K, T> {}
+
+/// An error returned by
+/// [`KeyNodeList::rebuild_from_links`](crate::KeyNodeList::rebuild_from_links)
+/// or [`KeyNodeList::with_node_links`](crate::KeyNodeList::with_node_links)
+/// when the links of the nodes do not form a valid list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkError<K> {
+  /// The node of the key links to a key that does not exist.
+  DanglingLink(K),
+  /// The node of the key links to a node that does not link back to it.
+  MismatchedLink(K),
+  /// The list is not empty but there is no node without a previous key.
+  NoFront,
+  /// There are multiple nodes without a previous key.
+  MultipleFronts,
+  /// Some nodes can not be reached from the front of the list,
+  /// for example, they form a cycle.
+  Unreachable,
+}
+
+impl<K: fmt::Debug> fmt::Display for LinkError<K> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::DanglingLink(key) => write!(f, "node {:?} links to a missing key", key),
+      Self::MismatchedLink(key) => {
+        write!(f, "node {:?} links to a node that does not link back", key)
+      }
+      Self::NoFront => write!(f, "no front node found"),
+      Self::MultipleFronts => write!(f, "multiple front nodes found"),
+      Self::Unreachable => write!(f, "some nodes are unreachable from the front"),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug> std::error::Error for LinkError<K> {}
@@ -1,7 +1,9 @@
+use crate::cursor::CursorMut;
 use crate::list::KeyNodeList;
 use crate::map::Map;
-use crate::node::Node;
-use std::hash::Hash;
+use crate::node::{Node, ValueNode};
+use alloc::vec;
+use core::hash::Hash;
 
 /// An owning iterator over the key-node paris of a [`KeyNodeList`].
 #[derive(Clone)]
@@ -21,6 +23,20 @@ where
   fn next(&mut self) -> Option<Self::Item> {
     self.list.pop_front()
   }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.list.linked_len();
+    (len, Some(len))
+  }
+}
+
+impl<K, N, M> ExactSizeIterator for IntoIter<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
 }
 
 /// An owning iterator over the keys of a [`KeyNodeList`].
@@ -41,6 +57,19 @@ where
   fn next(&mut self) -> Option<Self::Item> {
     self.iter.next().map(|(k, _)| k)
   }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<K, N, M> ExactSizeIterator for IntoKeys<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
 }
 
 /// An owning iterator over the nodes of a [`KeyNodeList`].
@@ -61,13 +90,76 @@ where
   fn next(&mut self) -> Option<Self::Item> {
     self.iter.next().map(|(_, n)| n)
   }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
 }
 
-/// An iterator over the key-node pairs of a [`KeyNodeList`].
+impl<K, N, M> ExactSizeIterator for IntoNodes<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+}
+
+/// An owning iterator over the values of a
+/// [`KeyValueList`](crate::KeyValueList).
 #[derive(Clone)]
+pub struct IntoValues<K, V, M> {
+  pub(crate) iter: IntoIter<K, ValueNode<K, V>, M>,
+}
+
+impl<K, V, M> Iterator for IntoValues<K, V, M>
+where
+  K: Hash + Eq + Clone,
+  M: Map<K, ValueNode<K, V>>,
+{
+  type Item = V;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|(_, n)| n.into_value())
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.iter.list.linked_len();
+    (len, Some(len))
+  }
+}
+
+impl<K, V, M> ExactSizeIterator for IntoValues<K, V, M>
+where
+  K: Hash + Eq + Clone,
+  M: Map<K, ValueNode<K, V>>,
+{
+}
+
+/// An iterator over the key-node pairs of a [`KeyNodeList`].
+///
+/// The remaining length is counted from [`KeyNodeList::linked_len`].
+/// Breaking the links through a broken implementation of [`Node`] is a
+/// logic error, and the remaining length may be inexact in that case.
 pub struct Iter<'a, K, N, M> {
   pub(crate) list: &'a KeyNodeList<K, N, M>,
   pub(crate) key: Option<&'a K>,
+  pub(crate) back: Option<&'a K>,
+  pub(crate) len: usize,
+}
+
+impl<'a, K, N, M> Clone for Iter<'a, K, N, M> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      list: self.list,
+      key: self.key,
+      back: self.back,
+      len: self.len,
+    }
+  }
 }
 
 impl<'a, K, N, M> Iterator for Iter<'a, K, N, M>
@@ -82,19 +174,124 @@ where
   fn next(&mut self) -> Option<Self::Item> {
     self.key.and_then(|k| {
       self.list.node(k).map(|n| {
-        self.key = n.next();
+        if self.back == Some(k) {
+          // the front and back keys met, the iteration is done
+          self.key = None;
+          self.back = None;
+        } else {
+          self.key = n.next();
+        }
+        self.len = self.len.saturating_sub(1);
+        (k, n)
+      })
+    })
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len, Some(self.len))
+  }
+}
+
+impl<'a, K, N, M> DoubleEndedIterator for Iter<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.back.and_then(|k| {
+      self.list.node(k).map(|n| {
+        if self.key == Some(k) {
+          // the front and back keys met, the iteration is done
+          self.key = None;
+          self.back = None;
+        } else {
+          self.back = n.prev();
+        }
+        self.len = self.len.saturating_sub(1);
         (k, n)
       })
     })
   }
 }
 
+impl<'a, K, N, M> ExactSizeIterator for Iter<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+}
+
+/// An iterator over the key-node pairs of a [`KeyNodeList`], starting at
+/// a specific key, created by [`KeyNodeList::iter_from`]. Reversed, it is
+/// also returned by [`KeyNodeList::riter_from`].
+///
+/// The number of remaining pairs is unknown without walking the list, so
+/// this iterator does not implement [`ExactSizeIterator`].
+pub struct IterFrom<'a, K, N, M> {
+  pub(crate) iter: Iter<'a, K, N, M>,
+}
+
+impl<'a, K, N, M> Clone for IterFrom<'a, K, N, M> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      iter: self.iter.clone(),
+    }
+  }
+}
+
+impl<'a, K, N, M> Iterator for IterFrom<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  type Item = (&'a K, &'a N);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    match self.iter.key {
+      Some(_) => (1, None),
+      None => (0, Some(0)),
+    }
+  }
+}
+
+impl<'a, K, N, M> DoubleEndedIterator for IterFrom<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back()
+  }
+}
+
 /// An iterator over the keys of a [`KeyNodeList`].
-#[derive(Clone)]
 pub struct Keys<'a, K, N, M> {
   pub(crate) iter: Iter<'a, K, N, M>,
 }
 
+impl<'a, K, N, M> Clone for Keys<'a, K, N, M> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      iter: self.iter.clone(),
+    }
+  }
+}
+
 impl<'a, K, N, M> Iterator for Keys<'a, K, N, M>
 where
   K: Hash + Eq,
@@ -107,14 +304,47 @@ where
   fn next(&mut self) -> Option<Self::Item> {
     self.iter.next().map(|(k, _)| k)
   }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'a, K, N, M> ExactSizeIterator for Keys<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+}
+
+impl<'a, K, N, M> DoubleEndedIterator for Keys<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(|(k, _)| k)
+  }
 }
 
 /// An iterator over the nodes of a [`KeyNodeList`].
-#[derive(Clone)]
 pub struct Nodes<'a, K, N, M> {
   pub(crate) iter: Iter<'a, K, N, M>,
 }
 
+impl<'a, K, N, M> Clone for Nodes<'a, K, N, M> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      iter: self.iter.clone(),
+    }
+  }
+}
+
 impl<'a, K, N, M> Iterator for Nodes<'a, K, N, M>
 where
   K: Hash + Eq,
@@ -127,4 +357,124 @@ where
   fn next(&mut self) -> Option<Self::Item> {
     self.iter.next().map(|(_, n)| n)
   }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'a, K, N, M> ExactSizeIterator for Nodes<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+}
+
+impl<'a, K, N, M> DoubleEndedIterator for Nodes<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(|(_, n)| n)
+  }
+}
+
+/// A mutable iterator over the key-node pairs of a [`KeyNodeList`],
+/// created by [`KeyNodeList::iter_mut`].
+pub struct IterMut<'a, K, N> {
+  pub(crate) pairs: vec::IntoIter<(&'a K, &'a mut N)>,
+}
+
+impl<'a, K, N> Iterator for IterMut<'a, K, N> {
+  type Item = (&'a K, &'a mut N);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.pairs.next()
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.pairs.size_hint()
+  }
+}
+
+impl<'a, K, N> DoubleEndedIterator for IterMut<'a, K, N> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.pairs.next_back()
+  }
+}
+
+impl<'a, K, N> ExactSizeIterator for IterMut<'a, K, N> {}
+
+/// A mutable iterator over the nodes of a [`KeyNodeList`],
+/// created by [`KeyNodeList::nodes_mut`].
+pub struct NodesMut<'a, K, N> {
+  pub(crate) iter: IterMut<'a, K, N>,
+}
+
+impl<'a, K, N> Iterator for NodesMut<'a, K, N> {
+  type Item = &'a mut N;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|(_, n)| n)
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'a, K, N> DoubleEndedIterator for NodesMut<'a, K, N> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(|(_, n)| n)
+  }
+}
+
+impl<'a, K, N> ExactSizeIterator for NodesMut<'a, K, N> {}
+
+/// An iterator that removes and yields the key-node pairs of a
+/// [`KeyNodeList`] that match a predicate, created by
+/// [`KeyNodeList::extract_if`].
+///
+/// Pairs are removed lazily as the iterator is advanced. If the iterator
+/// is dropped before it is exhausted, the pairs not yet visited are kept
+/// in the list, even if they match the predicate.
+pub struct ExtractIf<'a, K, N, M, F> {
+  pub(crate) cursor: CursorMut<'a, K, N, M>,
+  pub(crate) pred: F,
+}
+
+impl<'a, K, N, M, F> Iterator for ExtractIf<'a, K, N, M, F>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+  F: FnMut(&K, &N) -> bool,
+{
+  type Item = (K, N);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let (Some(k), Some(n)) = (self.cursor.key(), self.cursor.node()) {
+      if (self.pred)(k, n) {
+        return self.cursor.remove_current();
+      }
+      self.cursor.move_next();
+    }
+    None
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.cursor.list.len()))
+  }
 }
@@ -12,13 +12,17 @@
 //!
 //! # Examples
 //!
-//! ## Organizing key-value pairs using `KeyValueList`
+//! ## Organizing key-value pairs using `BTreeValueList`
+//!
+//! The examples use [`BTreeValueList`], which is available with or without
+//! the `std` feature. [`KeyValueList`], which is backed by a hash map, is
+//! used in the same way.
 //!
 //! ```
-//! use key_node_list::KeyValueList;
+//! use key_node_list::BTreeValueList;
 //!
 //! // construct key-value list from tuple array
-//! let mut list = KeyValueList::from([(1, "Reimu"), (2, "Marisa")]);
+//! let mut list = BTreeValueList::from([(1, "Reimu"), (2, "Marisa")]);
 //!
 //! // or pushing other key-value pairs to the front/back of list
 //! list.push_front(0, "Alice").unwrap();
@@ -48,14 +52,14 @@
 //! ## Editing list using cursors
 //!
 //! ```
-//! use key_node_list::KeyValueList;
+//! use key_node_list::BTreeValueList;
 //!
-//! let mut first_name = KeyValueList::from([
+//! let mut first_name = BTreeValueList::from([
 //!   (1, "Reimu".to_string()),
 //!   (2, "Marisa".to_string()),
 //! ]);
 //!
-//! let last_name = KeyValueList::from([(1, "Hakurei"), (2, "Kirisame")]);
+//! let last_name = BTreeValueList::from([(1, "Hakurei"), (2, "Kirisame")]);
 //!
 //! // append last names after first names
 //! let mut first_cur = first_name.cursor_front_mut();
@@ -71,11 +75,32 @@
 //! assert_eq!(vec, [(&1, "Reimu Hakurei"), (&2, "Marisa Kirisame")]);
 //! ```
 //!
+//! ## Looking up by borrowed keys
+//!
+//! Lookup methods such as [`KeyNodeList::node`] and
+//! [`KeyNodeList::contains_key`] accept any borrowed form of the key type,
+//! so a list with [`String`] keys can be queried with `&str` directly.
+//! Use [`KeyNodeList::cursor_at`] to get a cursor the same way.
+//!
+//! ```
+//! use key_node_list::BTreeValueList;
+//!
+//! let mut list: BTreeValueList<String, i32> = BTreeValueList::new();
+//! list.push_back("abc".to_string(), 1).unwrap();
+//! list.push_back("def".to_string(), 2).unwrap();
+//!
+//! assert_eq!(list.node("abc").map(|n| *n.value()), Some(1));
+//! assert!(list.contains_key("def"));
+//! assert_eq!(list["def"].value(), &2);
+//! assert_eq!(list.cursor_at("abc").next_key().map(String::as_str), Some("def"));
+//! ```
+//!
 //! ## Customizing your own nodes
 //!
 //! ```
 //! use key_node_list::{Node, impl_node};
 //! use key_node_list::KeyNodeList;
+//! use std::collections::BTreeMap;
 //!
 //! struct NameNode<'a> {
 //!   first: &'a str,
@@ -100,32 +125,116 @@
 //! impl_node!(NameNode<'a> { Key = i32, prev = prev, next = next });
 //!
 //! // create a `KeyNodeList` with node type `NameNode`
-//! let mut names: KeyNodeList<_, NameNode> = KeyNodeList::new();
+//! let mut names: KeyNodeList<_, NameNode, BTreeMap<_, _>> = KeyNodeList::new();
 //! names.push_back(1, ("Reimu", "Hakurei"));
 //! names.push_back(2, ("Marisa", "Kirisame"));
 //! assert_eq!(names[&1].first, "Reimu");
 //! assert_eq!(names[&2].last, "Kirisame");
 //! ```
+//!
+//! # `no_std` support
+//!
+//! `key_node_list` depends on the standard library through the `std`
+//! feature, which is enabled by default. Disable it to use this crate in
+//! `no_std` environments. In this case, [`KeyValueList`] and the default
+//! hash map of [`KeyNodeList`] are not available. Use
+//! [`BTreeValueList`] instead, or specify
+//! [`BTreeMap`](alloc::collections::BTreeMap) or your own [`Map`]
+//! implementation as the underlying map of [`KeyNodeList`].
+//!
+//! # Operation metrics
+//!
+//! Enable the `metrics` feature to count the structural operations (pushes,
+//! pops, insertions, removals and moves) performed on each list, see
+//! `KeyNodeList::op_stats`. `OpStats` lists how bulk operations are
+//! counted. Without the feature, no counters are stored.
+//!
+//! # Parallel iteration
+//!
+//! Enable the `rayon` feature to get `KeyNodeList::par_iter` and
+//! `KeyNodeList::par_nodes`, which return [rayon](https://docs.rs/rayon)
+//! parallel iterators over the list in list order.
+//!
+//! # Serialization
+//!
+//! Enable the `serde` feature to implement `Serialize` and `Deserialize`
+//! for [`KeyNodeList`], which is represented as a sequence of key-node
+//! pairs in list order. Nodes of [`KeyValueList`] are represented by their
+//! values only. Duplicate keys are rejected when deserializing.
+//!
+//! The feature also provides `KeyNodeList::key_order`, which
+//! returns a `KeyOrder` that serializes only the keys of the list in list
+//! order. A deserialized key order and a map can be combined into a list by
+//! [`KeyNodeList::from_map_with_order`].
+//!
+//! # LRU cache
+//!
+//! Enable the `cache` feature (which implies `std`) to get `LruCache`, a
+//! least-recently-used cache built on [`KeyValueList`] that reorders its
+//! pairs in *O*(1) time by [`KeyNodeList::move_to_back`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+mod bounded;
+mod builder;
+#[cfg(feature = "cache")]
+mod cache;
 mod cursor;
+mod entry;
+mod error;
 mod iter;
+mod link;
 mod list;
 mod map;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod node;
+mod order;
+#[cfg(feature = "rayon")]
+mod par;
+mod reversed;
+#[cfg(feature = "serde")]
+mod serde_impl;
 
+pub use bounded::*;
+pub use builder::*;
+#[cfg(feature = "cache")]
+pub use cache::*;
 pub use cursor::*;
+pub use entry::*;
+pub use error::*;
 pub use iter::*;
+pub use link::*;
 pub use list::*;
 pub use map::*;
+#[cfg(feature = "metrics")]
+pub use metrics::*;
 pub use node::*;
+pub use order::*;
+pub use reversed::*;
+#[cfg(feature = "serde")]
+pub use serde_impl::*;
 
 /// A [`KeyNodeList`] that uses [`ValueNode<K, V>`] as its node type and
 /// [`HashMap`](std::collections::HashMap) as its underlying hash map.
 ///
 /// `KeyValueList` stores key-value pairs and organize them in the form of
 /// a doubly-linked list.
+#[cfg(feature = "std")]
 pub type KeyValueList<K, V> = KeyNodeList<K, ValueNode<K, V>>;
 
+/// A [`KeyNodeList`] that uses [`ValueNode<K, V>`] as its node type and
+/// [`BTreeMap`](alloc::collections::BTreeMap) as its underlying map.
+///
+/// Unlike [`KeyValueList`], the underlying map iterates over the pairs in
+/// the order of their keys, and `BTreeValueList` is also available in
+/// `no_std` environments. Keys must implement [`Ord`] in addition to
+/// [`Hash`](core::hash::Hash) and [`Eq`].
+pub type BTreeValueList<K, V> =
+  KeyNodeList<K, ValueNode<K, V>, alloc::collections::BTreeMap<K, ValueNode<K, V>>>;
+
 /// Gets a mutable reference of the previous pointer of the specific node.
 macro_rules! node_prev_mut {
   ($list:expr, $key:expr) => {
@@ -157,10 +266,24 @@ pub(crate) use node_next_mut;
 #[cfg(test)]
 mod test {
   use super::*;
+  use alloc::collections::BTreeMap;
+  use alloc::string::{String, ToString};
+  use alloc::vec::Vec;
+  use alloc::{format, vec};
+
+  /// Collects the keys of `list` in list order, and checks that following
+  /// the previous keys from the back visits the same keys in reverse.
+  fn linked_keys<V>(list: &BTreeValueList<i32, V>) -> Vec<i32> {
+    let keys: Vec<_> = list.keys().copied().collect();
+    let mut rev: Vec<_> = list.keys().rev().copied().collect();
+    rev.reverse();
+    assert_eq!(keys, rev);
+    keys
+  }
 
   #[test]
   fn test_capacity() {
-    let mut list = KeyValueList::new();
+    let mut list = BTreeValueList::new();
     assert_eq!(list.front_key(), None);
     assert_eq!(list.back_key(), None);
     assert_eq!(list.len(), 0);
@@ -184,7 +307,7 @@ mod test {
 
   #[test]
   fn test_push_into_iter() {
-    let mut list = KeyValueList::new();
+    let mut list = BTreeValueList::new();
     for i in 0..10 {
       list.push_back(i, i).unwrap();
     }
@@ -198,7 +321,7 @@ mod test {
 
   #[test]
   fn test_push_pop() {
-    let mut list = KeyValueList::new();
+    let mut list = BTreeValueList::new();
     assert_eq!(list.pop_back(), None);
     assert_eq!(list.pop_front(), None);
     for i in 0..10 {
@@ -215,7 +338,7 @@ mod test {
 
   #[test]
   fn test_push_front_iter() {
-    let mut list = KeyValueList::new();
+    let mut list = BTreeValueList::new();
     for i in 0..10 {
       list.push_front(i, i * 2).unwrap();
     }
@@ -233,7 +356,7 @@ mod test {
 
   #[test]
   fn test_cursor_move() {
-    let mut list = KeyValueList::new();
+    let mut list = BTreeValueList::new();
     for i in 0..10 {
       list.push_front(i, i * 2).unwrap();
     }
@@ -241,7 +364,9 @@ mod test {
     assert_eq!(cur.key(), Some(&0));
     cur.move_prev();
     assert_eq!(cur.key(), Some(&1));
+    #[cfg(feature = "std")]
     dbg!(cur.key());
+    #[cfg(feature = "std")]
     dbg!(cur.node());
     assert_eq!(cur.next_key(), Some(&0));
     cur.move_next();
@@ -254,7 +379,7 @@ mod test {
 
   #[test]
   fn test_cursor_insert_remove() {
-    let mut list = KeyValueList::new();
+    let mut list = BTreeValueList::new();
     for i in 0..10 {
       list.push_back(i * 10, i * 10).unwrap();
     }
@@ -286,16 +411,1987 @@ mod test {
 
   #[test]
   fn test_from_eq() {
-    let list1 = KeyValueList::from([(1, 1), (2, 2), (3, 3)]);
-    let list2 = KeyValueList::from([(1, 1), (2, 2), (3, 3)]);
-    let list3 = KeyValueList::from([(1, 1), (2, 2), (3, 4)]);
-    let list4: KeyValueList<i32, i32> = [(1, 1), (2, 2), (3, 4)].into_iter().collect();
-    let list5: KeyValueList<i32, ()> = [1, 2, 3].into_iter().collect();
-    let mut list6: KeyValueList<i32, ()> = KeyValueList::new();
+    let list1 = BTreeValueList::from([(1, 1), (2, 2), (3, 3)]);
+    let list2 = BTreeValueList::from([(1, 1), (2, 2), (3, 3)]);
+    let list3 = BTreeValueList::from([(1, 1), (2, 2), (3, 4)]);
+    let list4: BTreeValueList<i32, i32> = [(1, 1), (2, 2), (3, 4)].into_iter().collect();
+    let list5: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    let mut list6: BTreeValueList<i32, ()> = BTreeValueList::new();
     list6.extend([1, 2, 3]);
     assert_eq!(list1, list2);
     assert_ne!(list1, list3);
     assert_eq!(list3, list4);
     assert_eq!(list5, list6);
   }
+
+  #[test]
+  fn test_binary_search_by() {
+    let list = BTreeValueList::from([(1, 10), (2, 20), (3, 20), (4, 40)]);
+    assert_eq!(list.binary_search_by(|v| v.cmp(&20)), Ok(2));
+    assert_eq!(list.binary_search_by(|v| v.cmp(&40)), Ok(4));
+    assert_eq!(list.binary_search_by(|v| v.cmp(&5)), Err(0));
+    assert_eq!(list.binary_search_by(|v| v.cmp(&30)), Err(3));
+    assert_eq!(list.binary_search_by(|v| v.cmp(&50)), Err(4));
+  }
+
+  #[test]
+  fn test_iter_with_links() {
+    let list: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    let links: Vec<_> = list.iter_with_links().collect();
+    assert_eq!(
+      links,
+      [
+        (&1, None, Some(&2)),
+        (&2, Some(&1), Some(&3)),
+        (&3, Some(&2), None),
+      ]
+    );
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_try_reserve() {
+    let mut list: KeyValueList<i32, i32> = KeyValueList::new();
+    assert!(list.try_reserve(100).is_ok());
+    assert!(list.nodes.capacity() >= 100);
+    assert!(list.try_reserve(usize::MAX).is_err());
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn test_cursor_replace_current() {
+    let mut list = BTreeValueList::from([(1, 1), (2, 2), (3, 3)]);
+    let mut cur = list.cursor_mut(2);
+    assert_eq!(cur.replace_current(20).map(|n| n.into_value()), Some(2));
+    assert_eq!(cur.node().map(|n| *n.value()), Some(20));
+    assert_eq!(cur.prev_key(), Some(&1));
+    assert_eq!(cur.next_key(), Some(&3));
+    cur.move_next();
+    cur.move_next();
+    assert!(cur.replace_current(0).is_none());
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, 1), (2, 20), (3, 3)]);
+  }
+
+  #[test]
+  fn test_iter_clone() {
+    struct Payload;
+    let list: BTreeValueList<i32, Payload> = [(1, Payload), (2, Payload), (3, Payload)].into();
+    let mut iter = list.keys();
+    assert_eq!(iter.next(), Some(&1));
+    let checkpoint = iter.clone();
+    assert_eq!(iter.collect::<Vec<_>>(), [&2, &3]);
+    assert_eq!(checkpoint.collect::<Vec<_>>(), [&2, &3]);
+    assert_eq!(list.nodes().clone().count(), 3);
+    assert_eq!(list.iter().skip(2).clone().next().map(|(k, _)| k), Some(&3));
+  }
+
+  #[test]
+  fn test_append_renaming() {
+    let mut list = BTreeValueList::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+    let other = BTreeValueList::from([(2, 'd'), (4, 'e'), (5, 'f')]);
+    list.append_renaming(other, |k| k + 1);
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(
+      vec,
+      [(1, 'a'), (2, 'b'), (3, 'c'), (6, 'd'), (4, 'e'), (5, 'f')]
+    );
+    let mut cur = list.cursor_back();
+    for k in [5, 4, 6, 3, 2, 1] {
+      assert_eq!(cur.key(), Some(&k));
+      cur.move_prev();
+    }
+    assert!(cur.is_null());
+  }
+
+  #[test]
+  fn test_endpoint_mut_with_neighbor() {
+    let mut list = BTreeValueList::new();
+    assert!(list.front_mut_with_next_key().is_none());
+    list.push_back(1, 1).unwrap();
+    let (node, next) = list.front_mut_with_next_key().unwrap();
+    *node.value_mut() = 10;
+    assert_eq!(next, None);
+    list.push_back(2, 2).unwrap();
+    list.push_back(3, 3).unwrap();
+    let (node, next) = list.front_mut_with_next_key().unwrap();
+    *node.value_mut() += 1;
+    assert_eq!(next, Some(2));
+    let (node, prev) = list.back_mut_with_prev_key().unwrap();
+    *node.value_mut() = 30;
+    assert_eq!(prev, Some(2));
+    assert_eq!(list[&1].value(), &11);
+    assert_eq!(list[&3].value(), &30);
+  }
+
+  #[test]
+  fn test_cursor_reborrow() {
+    fn bump_next<M>(mut cur: CursorMut<i32, ValueNode<i32, i32>, M>)
+    where
+      M: Map<i32, ValueNode<i32, i32>>,
+    {
+      cur.move_next();
+      *cur.node_mut().unwrap().value_mut() += 100;
+    }
+
+    let mut list = BTreeValueList::from([(1, 1), (2, 2), (3, 3)]);
+    let mut cur = list.cursor_front_mut();
+    bump_next(cur.reborrow());
+    // the reborrowed cursor shares its position with the parent
+    assert_eq!(cur.key(), Some(&2));
+    assert_eq!(cur.node().map(|n| *n.value()), Some(102));
+    bump_next(cur.reborrow());
+    assert_eq!(cur.key(), Some(&3));
+    assert_eq!(list[&3].value(), &103);
+  }
+
+  #[test]
+  fn test_cursor_reborrow_remove() {
+    let mut list = BTreeValueList::from([(1, 1), (2, 2), (3, 3)]);
+    let mut cur = list.cursor_mut(2);
+    assert_eq!(cur.reborrow().remove_current().map(|(k, _)| k), Some(2));
+    // the parent does not keep the removed key
+    assert!(!cur.is_null());
+    assert_eq!(cur.key(), Some(&3));
+    cur.insert_after(10, 10).unwrap();
+    assert_eq!(cur.remove_current().map(|(k, _)| k), Some(3));
+    assert_eq!(cur.key(), Some(&10));
+    // removing the back pair through the reborrowed cursor
+    cur.reborrow().remove_current();
+    assert!(cur.is_null());
+    assert!(cur.remove_current().is_none());
+    cur.insert_after(20, 20).unwrap();
+    assert_eq!(linked_keys(&list), [20, 1]);
+  }
+
+  #[test]
+  fn test_values_eq() {
+    let list1 = BTreeValueList::from([(1, 1), (2, 2), (3, 3)]);
+    let mut list2 = BTreeValueList::from([(2, 2), (3, 3)]);
+    list2.push_front(1, 1).unwrap();
+    assert!(list1.values_eq(&list2));
+    let list3 = BTreeValueList::from([(1, 1), (3, 3), (2, 2)]);
+    assert!(!list1.values_eq(&list3));
+    let list4 = BTreeValueList::from([(1, 1), (2, 2)]);
+    assert!(!list1.values_eq(&list4));
+    assert!(!list4.values_eq(&list1));
+  }
+
+  #[test]
+  fn test_get_key_node() {
+    let list = BTreeValueList::from([("Reimu".to_string(), 1), ("Marisa".to_string(), 2)]);
+    let (k, n) = list.get_key_node("Marisa").unwrap();
+    assert_eq!(k, &"Marisa".to_string());
+    assert_eq!(n.value(), &2);
+    assert!(list.get_key_node("Alice").is_none());
+  }
+
+  #[test]
+  fn test_bounded_list() {
+    let mut list: BoundedList<i32, ValueNode<i32, i32>, BTreeMap<_, _>> = BoundedList::new(3);
+    for i in 0..3 {
+      assert_eq!(list.push_back(i, i).map_err(|_| ()), Ok(None));
+    }
+    let evicted = list.push_back(3, 3).ok().flatten();
+    assert_eq!(evicted.map(|(k, n)| (k, n.into_value())), Some((0, 0)));
+    assert!(list.push_back(3, 30).is_err());
+    let evicted = list.push_front(0, 0).ok().flatten();
+    assert_eq!(evicted.map(|(k, n)| (k, n.into_value())), Some((3, 3)));
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [0, 1, 2]);
+    let evicted: Vec<_> = list.set_max_len(1).into_iter().map(|(k, _)| k).collect();
+    assert_eq!(evicted, [0, 1]);
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.max_len(), 1);
+    assert_eq!(list.front_key(), Some(&2));
+    assert_eq!(list.back_key(), Some(&2));
+  }
+
+  #[test]
+  fn test_pop_links() {
+    let mut list = BTreeValueList::from([(1, 1), (2, 2), (3, 3), (4, 4)]);
+    assert_eq!(list.pop_front().map(|(k, _)| k), Some(1));
+    assert_eq!(list.front_key(), Some(&2));
+    assert_eq!(list.front_node().unwrap().prev(), None);
+    assert_eq!(list.pop_back().map(|(k, _)| k), Some(4));
+    assert_eq!(list.back_key(), Some(&3));
+    assert_eq!(list.back_node().unwrap().next(), None);
+    assert_eq!(list.keys().rev().copied().collect::<Vec<_>>(), [3, 2]);
+    // popping the only pair from either end clears both ends
+    let mut list = BTreeValueList::from([(1, 1)]);
+    list.pop_back();
+    assert_eq!(list.front_key(), None);
+    assert_eq!(list.back_key(), None);
+    let mut list = BTreeValueList::from([(1, 1)]);
+    list.pop_front();
+    assert_eq!(list.front_key(), None);
+    assert_eq!(list.back_key(), None);
+    list.push_back(2, 2).unwrap();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [2]);
+  }
+
+  #[test]
+  fn test_iter_mut_rev() {
+    let mut list = BTreeValueList::from([(1, 1), (2, 1), (3, 1), (4, 1)]);
+    let mut acc = 0;
+    let mut visited = Vec::new();
+    for (k, n) in list.iter_mut_rev() {
+      acc += *n.value();
+      *n.value_mut() = acc;
+      visited.push(*k);
+    }
+    assert_eq!(visited, [4, 3, 2, 1]);
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, 4), (2, 3), (3, 2), (4, 1)]);
+  }
+
+  #[test]
+  fn test_has_cycle() {
+    let mut list: BTreeValueList<i32, ()> = BTreeValueList::new();
+    assert!(!list.has_cycle());
+    list.extend([1, 2, 3, 4]);
+    assert!(!list.has_cycle());
+    *node_next_mut!(list.node_mut(&4).unwrap()) = Some(2);
+    assert!(list.has_cycle());
+    *node_next_mut!(list.node_mut(&1).unwrap()) = Some(1);
+    assert!(list.has_cycle());
+  }
+
+  #[test]
+  fn test_get_index() {
+    let list = BTreeValueList::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    assert_eq!(list.get_index_of("a"), Some(0));
+    assert_eq!(list.get_index_of("b"), Some(1));
+    assert_eq!(list.get_index_of("c"), None);
+    let (k, n) = list.get_index(1).unwrap();
+    assert_eq!((k.as_str(), *n.value()), ("b", 2));
+    assert!(list.get_index(2).is_none());
+  }
+
+  #[test]
+  fn test_push_back_with_key() {
+    let mut list = BTreeValueList::from([(1, "1".to_string())]);
+    let node = list
+      .push_back_with_key(2, |k| ValueNode::new(k.to_string()))
+      .unwrap();
+    node.value_mut().push('!');
+    assert_eq!(list.back_node().unwrap().value(), "2!");
+    assert_eq!(list.back_node().unwrap().prev(), Some(&1));
+    let result = list.push_back_with_key(1, |_| unreachable!());
+    assert_eq!(result.err(), Some(1));
+    assert_eq!(list.len(), 2);
+  }
+
+  #[test]
+  fn test_range_rev() {
+    let list: BTreeValueList<i32, ()> = (1..=5).collect();
+    let keys = |from, to| {
+      list
+        .range_rev(&from, &to)
+        .map(|(k, _)| *k)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(keys(4, 2), [4, 3, 2]);
+    assert_eq!(keys(3, 3), [3]);
+    assert_eq!(keys(2, 4), [2, 1]);
+    assert_eq!(keys(5, 0), [5, 4, 3, 2, 1]);
+    assert!(keys(0, 1).is_empty());
+  }
+
+  #[test]
+  fn test_debug_keys() {
+    let list: BTreeValueList<i32, &str> = [(1, "a"), (2, "b"), (3, "c")].into();
+    assert_eq!(format!("{:?}", list.debug_keys()), "[1, 2, 3]");
+    let list = BTreeValueList::<i32, ()>::new();
+    assert_eq!(format!("{:?}", list.debug_keys()), "[]");
+  }
+
+  #[test]
+  fn test_cursor_insert_reporting() {
+    let mut list: BTreeValueList<i32, i32> = [(1, 1), (3, 3)].into();
+    let mut cur = list.cursor_mut(1);
+    let report = cur.insert_after_reporting(2, 2).unwrap();
+    assert_eq!(
+      report,
+      InsertReport {
+        prev: Some(1),
+        next: Some(3)
+      }
+    );
+    let report = cur.insert_before_reporting(0, 0).unwrap();
+    assert_eq!(
+      report,
+      InsertReport {
+        prev: None,
+        next: Some(1)
+      }
+    );
+    assert_eq!(cur.insert_after_reporting(3, 3), Err((3, 3)));
+    cur.move_next();
+    cur.move_next();
+    cur.move_next();
+    assert!(cur.is_null());
+    let report = cur.insert_after_reporting(-1, -1).unwrap();
+    assert_eq!(
+      report,
+      InsertReport {
+        prev: None,
+        next: Some(0)
+      }
+    );
+    let report = cur.insert_before_reporting(4, 4).unwrap();
+    assert_eq!(
+      report,
+      InsertReport {
+        prev: Some(3),
+        next: None
+      }
+    );
+    assert_eq!(
+      list.keys().copied().collect::<Vec<_>>(),
+      [-1, 0, 1, 2, 3, 4]
+    );
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_recycle_into() {
+    let mut list: KeyValueList<i32, i32> = (0..100).map(|i| (i, i * 2)).collect();
+    let mut pairs = Vec::new();
+    let map = list.recycle_into(|k, n| pairs.push((k, *n.value())));
+    assert_eq!(pairs, (0..100).map(|i| (i, i * 2)).collect::<Vec<_>>());
+    assert!(map.is_empty());
+    assert!(map.capacity() > 0);
+    list = KeyValueList::with_map(map);
+    list.push_back(1, 1).unwrap();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1]);
+  }
+
+  #[test]
+  fn test_take_while_prefix() {
+    let mut list: BTreeValueList<i32, i32> = [(1, 1), (2, 2), (3, 5), (4, 4)].into();
+    let prefix = list.take_while_prefix(|k, n| k == n.value());
+    assert_eq!(prefix.keys().copied().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [3, 4]);
+    assert_eq!(prefix.back_node().unwrap().next(), None);
+    assert_eq!(list.front_node().unwrap().prev(), None);
+    assert!(list.take_while_prefix(|k, n| k == n.value()).is_empty());
+    let prefix = list.take_while_prefix(|_, _| true);
+    assert_eq!(prefix.keys().copied().collect::<Vec<_>>(), [3, 4]);
+    assert!(list.is_empty());
+  }
+
+  #[test]
+  fn test_order_index() {
+    let mut list: BTreeValueList<i32, i32> = [(3, 3), (1, 1), (2, 2)].into();
+    let index = list.reindex();
+    assert!(index.order_key(&list, &3) < index.order_key(&list, &1));
+    assert!(index.order_key(&list, &1) < index.order_key(&list, &2));
+    assert_eq!(index.order_key(&list, &4), None);
+    assert_eq!(index.is_before(&list, &3, &2), Some(true));
+    assert_eq!(index.is_before(&list, &2, &1), Some(false));
+    assert_eq!(index.is_before(&list, &2, &4), None);
+    // mutating nodes in place keeps the index valid
+    *list.node_mut(&1).unwrap().value_mut() = 10;
+    assert!(!index.is_stale(&list));
+    assert_eq!(index.is_before(&list, &3, &1), Some(true));
+    // structural mutations make the index stale
+    list.move_to_back(&3);
+    assert!(index.is_stale(&list));
+    assert_eq!(index.order_key(&list, &3), None);
+    assert_eq!(index.is_before(&list, &1, &3), None);
+    let index = list.reindex();
+    assert_eq!(index.is_before(&list, &1, &3), Some(true));
+    list.pop_front();
+    assert!(index.is_stale(&list));
+    let index = list.reindex();
+    list.clear();
+    assert!(index.is_stale(&list));
+  }
+
+  #[test]
+  fn test_try_index() {
+    let list: BTreeValueList<String, i32> = [("a".to_string(), 1)].into();
+    assert_eq!(list.try_index("a").unwrap().value(), &1);
+    let err = list.try_index("b").unwrap_err();
+    assert_eq!(err, MissingKey("b".to_string()));
+    assert_eq!(err.to_string(), "no entry found for key \"b\"");
+  }
+
+  #[test]
+  fn test_apply_updates() {
+    let mut list: BTreeValueList<i32, &str> = [(1, "a"), (2, "b"), (3, "c")].into();
+    list.apply_updates([(2, "B"), (4, "d"), (1, "A"), (4, "D")]);
+    assert_eq!(
+      list
+        .iter()
+        .map(|(k, n)| (*k, *n.value()))
+        .collect::<Vec<_>>(),
+      [(1, "A"), (2, "B"), (3, "c"), (4, "D")],
+    );
+  }
+
+  #[test]
+  fn test_cursor_key_cloned() {
+    let mut list: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    let mut cur = list.cursor_mut(2);
+    let next = cur.next_key_cloned().unwrap();
+    let prev = cur.prev_key_cloned().unwrap();
+    cur.remove_current();
+    assert_eq!((prev, next), (1, 3));
+    let cur = list.cursor(3);
+    assert_eq!(cur.next_key_cloned(), None);
+    assert_eq!(cur.prev_key_cloned(), Some(1));
+  }
+
+  #[test]
+  fn test_unreachable_keys() {
+    let mut list: BTreeValueList<i32, ()> = [1, 2, 3, 4].into_iter().collect();
+    assert!(list.unreachable_keys().is_empty());
+    *node_next_mut!(list.node_mut(&2).unwrap()) = Some(4);
+    assert_eq!(list.unreachable_keys(), [&3]);
+    *node_next_mut!(list.node_mut(&1).unwrap()) = None;
+    let mut keys = list.unreachable_keys();
+    keys.sort();
+    assert_eq!(keys, [&2, &3, &4]);
+  }
+
+  #[test]
+  fn test_contains_all_any() {
+    let list: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    assert!(list.contains_all([1, 3]));
+    assert!(!list.contains_all([1, 4]));
+    assert!(list.contains_all([]));
+    assert!(list.contains_any([4, 2]));
+    assert!(!list.contains_any([4, 5]));
+    assert!(!list.contains_any([]));
+  }
+
+  #[test]
+  fn test_split_first_last() {
+    let list: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    let ((k, _), rest) = list.split_first().unwrap();
+    assert_eq!(k, 1);
+    assert_eq!(rest.keys().copied().collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(rest.front_node().unwrap().prev(), None);
+    let ((k, _), rest) = rest.split_last().unwrap();
+    assert_eq!(k, 3);
+    assert_eq!(rest.back_key(), Some(&2));
+    assert_eq!(rest.back_node().unwrap().next(), None);
+    let ((k, _), rest) = rest.split_last().unwrap();
+    assert_eq!(k, 2);
+    assert!(rest.is_empty());
+    assert!(rest.split_first().is_none());
+  }
+
+  #[test]
+  fn test_cursor_lookahead() {
+    let mut list: BTreeValueList<i32, ()> = [1, 2, 3, 4].into_iter().collect();
+    let keys =
+      |v: Vec<(&i32, &ValueNode<i32, ()>)>| v.into_iter().map(|(k, _)| *k).collect::<Vec<_>>();
+    let cur = list.cursor(2);
+    assert_eq!(keys(cur.lookahead(2)), [2, 3]);
+    assert_eq!(keys(cur.lookahead(10)), [2, 3, 4]);
+    assert_eq!(keys(cur.lookbehind(10)), [2, 1]);
+    assert!(cur.lookahead(0).is_empty());
+    assert!(list.cursor(5).lookahead(2).is_empty());
+    let cur = list.cursor_mut(4);
+    assert_eq!(keys(cur.lookbehind(3)), [4, 3, 2]);
+  }
+
+  #[test]
+  fn test_fold() {
+    let list: BTreeValueList<i32, i32> = [(1, 10), (2, 20), (3, 30)].into();
+    let f = |mut acc: Vec<i32>, k: &i32, n: &ValueNode<i32, i32>| {
+      acc.push(k + n.value());
+      acc
+    };
+    assert_eq!(list.fold(Vec::new(), f), [11, 22, 33]);
+    assert_eq!(list.rfold(Vec::new(), f), [33, 22, 11]);
+    assert_eq!(
+      BTreeValueList::<i32, i32>::new().rfold(0, |acc, _, _| acc + 1),
+      0
+    );
+  }
+
+  #[test]
+  fn test_insert_sorted() {
+    let mut list = BTreeValueList::new();
+    for (k, v) in [(1, 5), (2, 3), (3, 8), (4, 5), (5, 1)] {
+      list.insert_sorted(k, v).unwrap();
+    }
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [5, 2, 1, 4, 3]);
+    assert_eq!(list.insert_sorted(3, 0), Err((3, 0)));
+    assert_eq!(list.back_node().unwrap().next(), None);
+  }
+
+  #[test]
+  fn test_reversed_view() {
+    let list: BTreeValueList<i32, i32> = [(1, 10), (2, 20), (3, 30)].into();
+    let view = list.reversed_view();
+    assert_eq!(view.front_key(), Some(&3));
+    assert_eq!(view.back_node().map(|n| *n.value()), Some(10));
+    assert_eq!(view.len(), 3);
+    assert_eq!(view.keys().copied().collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!(
+      format!("{:?}", view.iter().map(|(k, _)| k).collect::<Vec<_>>()),
+      "[3, 2, 1]"
+    );
+    let mut cur = view.cursor_front();
+    assert_eq!(cur.key(), Some(&3));
+    assert_eq!(cur.next_key(), Some(&2));
+    cur.move_next();
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    assert_eq!(cur.next_key(), None);
+    cur.move_next();
+    assert!(cur.is_null());
+    cur.move_prev();
+    assert_eq!(cur.key(), Some(&1));
+    assert_eq!(view.cursor(2).prev_node().map(|n| *n.value()), Some(30));
+    // the iterator of the view is exact-sized and double-ended
+    let mut iter = view.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next().map(|(k, _)| *k), Some(3));
+    assert_eq!(iter.next_back().map(|(k, _)| *k), Some(1));
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    assert_eq!(iter.next_back().map(|(k, _)| *k), Some(2));
+    assert_eq!((iter.len(), iter.next(), iter.next_back()), (0, None, None));
+    let keys: Vec<_> = view.iter().rev().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [1, 2, 3]);
+  }
+
+  #[test]
+  fn test_pop_n() {
+    let mut list: BTreeValueList<i32, ()> = (1..=6).collect();
+    let keys =
+      |v: Vec<(i32, ValueNode<i32, ()>)>| v.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+    assert_eq!(keys(list.pop_front_n(2)), [1, 2]);
+    assert_eq!(keys(list.pop_back_n(2)), [6, 5]);
+    assert_eq!(keys(list.pop_front_n(0)), []);
+    assert_eq!(keys(list.pop_front_n(5)), [3, 4]);
+    assert!(list.is_empty());
+    assert!(list.pop_back_n(1).is_empty());
+  }
+
+  #[test]
+  fn test_clone_range() {
+    let list: BTreeValueList<i32, i32> = (1..=5).map(|i| (i, i * 10)).collect();
+    let range = list.clone_range(&2, &4).unwrap();
+    assert_eq!(range.keys().copied().collect::<Vec<_>>(), [2, 3, 4]);
+    assert_eq!(range.front_node().unwrap().prev(), None);
+    assert_eq!(range.back_node().unwrap().next(), None);
+    assert_eq!(range[&3].value(), &30);
+    assert_eq!(list.clone_range(&3, &3).unwrap().len(), 1);
+    assert!(list.clone_range(&4, &2).is_none());
+    assert!(list.clone_range(&0, &2).is_none());
+    assert_eq!(list.len(), 5);
+  }
+
+  #[test]
+  fn test_extend_try() {
+    #[derive(Debug, PartialEq)]
+    struct Even(i32);
+    impl TryFrom<i32> for ValueNode<i32, Even> {
+      type Error = String;
+      fn try_from(i: i32) -> Result<Self, Self::Error> {
+        if i % 2 == 0 {
+          Ok(Even(i).into())
+        } else {
+          Err(format!("{} is odd", i))
+        }
+      }
+    }
+    let mut list: BTreeValueList<i32, Even> = BTreeValueList::new();
+    assert_eq!(list.extend_try([(1, 2), (2, 4)]), Ok(()));
+    let err = list.extend_try([(3, 6), (4, 7), (5, 8)]).unwrap_err();
+    assert_eq!((err.index(), err.key()), (1, &4));
+    assert_eq!(
+      err.to_string(),
+      "failed to convert item 1 with key 4: 7 is odd"
+    );
+    let err = list.extend_try([(5, 8), (2, 10)]).unwrap_err();
+    assert!(matches!(
+      err,
+      ExtendError::DuplicateKey {
+        index: 1,
+        key: 2,
+        ..
+      }
+    ));
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 5]);
+  }
+
+  #[test]
+  fn test_node_at_index_mut() {
+    let mut list: BTreeValueList<i32, i32> = [(3, 0), (1, 0), (2, 0)].into();
+    *list.node_at_index_mut(1).unwrap().value_mut() = 42;
+    assert_eq!(list[&1].value(), &42);
+    assert!(list.node_at_index_mut(3).is_none());
+  }
+
+  #[test]
+  fn test_append_self_clone() {
+    let mut list: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    let other = list.clone();
+    assert!(!list.is_disjoint(&other));
+    assert!(list.is_disjoint(&BTreeValueList::<i32, i32>::from([(4, 4)])));
+    list.append_renaming(other, |k| k + 10);
+    assert_eq!(
+      list.keys().copied().collect::<Vec<_>>(),
+      [1, 2, 3, 11, 12, 13]
+    );
+    assert!(!list.has_cycle());
+    assert!(list.unreachable_keys().is_empty());
+    // appending a clone is rejected before anything is moved
+    let mut other = list.clone();
+    let err = list.append(&mut other).unwrap_err();
+    assert_eq!(err, KeyCollision(vec![1, 2, 3, 11, 12, 13]));
+    assert_eq!(list.len(), 6);
+    assert_eq!(other.len(), 6);
+    assert_eq!(list.back_node().unwrap().next(), None);
+    assert_eq!(other.front_node().unwrap().prev(), None);
+  }
+
+  #[test]
+  fn test_builder() {
+    let mut builder = KeyNodeListBuilder::<i32, ValueNode<i32, i32>, BTreeMap<_, _>>::new();
+    builder
+      .push(1, 10)
+      .unwrap()
+      .push(2, 20)
+      .unwrap()
+      .push(3, 30)
+      .unwrap();
+    assert_eq!(builder.push(2, 0).err(), Some((2, 0)));
+    assert_eq!(builder.len(), 3);
+    let list = builder.build();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(list[&2].value(), &20);
+  }
+
+  #[test]
+  fn test_cursor_rotate_current_to_back() {
+    let mut list: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    let mut cur = list.cursor_mut(1);
+    cur.rotate_current_to_back();
+    assert_eq!(cur.key(), Some(&2));
+    cur.rotate_current_to_back();
+    assert_eq!(cur.key(), Some(&3));
+    cur.move_next();
+    cur.move_next();
+    cur.rotate_current_to_back();
+    assert!(cur.is_null());
+    cur.rotate_current_to_back();
+    assert!(cur.is_null());
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [3, 1, 2]);
+    let rev: Vec<_> = list.iter_mut_rev().map(|(k, _)| *k).collect();
+    assert_eq!(rev, [2, 1, 3]);
+    assert_eq!(list.front_node().unwrap().prev(), None);
+  }
+
+  #[test]
+  fn test_into_values() {
+    let list: BTreeValueList<i32, &str> = [(2, "b"), (1, "a"), (3, "c")].into();
+    let values = list.into_values();
+    assert_eq!(values.size_hint(), (3, Some(3)));
+    assert_eq!(values.collect::<Vec<_>>(), ["b", "a", "c"]);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_cursor_eq_hash() {
+    use std::collections::HashSet;
+    let list: KeyValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+    let other = list.clone();
+    let mut cur = list.cursor(1);
+    assert_eq!(cur.position_key(), Some(&1));
+    assert_ne!(cur, list.cursor(2));
+    cur.move_next();
+    assert_eq!(cur, list.cursor(2));
+    assert_ne!(cur, other.cursor(2));
+    let set: HashSet<_> = [list.cursor(1), list.cursor(1), other.cursor(1)].into();
+    assert_eq!(set.len(), 2);
+  }
+
+  #[test]
+  fn test_reorder() {
+    let mut list: BTreeValueList<i32, i32> = (1..=4).map(|i| (i, i)).collect();
+    assert_eq!(
+      list.reorder(&[1, 2]),
+      Err(ReorderError::LengthMismatch {
+        expected: 4,
+        found: 2
+      })
+    );
+    assert_eq!(
+      list.reorder(&[1, 2, 5, 4]),
+      Err(ReorderError::UnknownKey(2))
+    );
+    assert_eq!(
+      list.reorder(&[1, 2, 1, 4]),
+      Err(ReorderError::DuplicateKey(2))
+    );
+    // the first repeated key is reported
+    assert_eq!(
+      list.reorder(&[2, 1, 2, 1]),
+      Err(ReorderError::DuplicateKey(2))
+    );
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    list.reorder(&[3, 1, 4, 2]).unwrap();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [3, 1, 4, 2]);
+    assert_eq!(
+      list.rfold(Vec::new(), |mut v, k, _| {
+        v.push(*k);
+        v
+      }),
+      [2, 4, 1, 3]
+    );
+    assert_eq!(list[&4].value(), &4);
+  }
+
+  #[test]
+  fn test_is_front_back() {
+    let mut list: BTreeValueList<String, ()> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+    assert!(list.is_front("a") && !list.is_back("a"));
+    assert!(list.is_back("b") && !list.is_front("b"));
+    assert!(!list.is_front("c"));
+    list.clear();
+    assert!(!list.is_front("a") && !list.is_back("b"));
+  }
+
+  #[test]
+  fn test_cursor_seek_forward_bounded() {
+    let list: BTreeValueList<i32, ()> = (1..=5).collect();
+    let mut cur = list.cursor(1);
+    assert_eq!(cur.seek_forward_bounded(&4, 2), None);
+    assert_eq!(cur.key(), Some(&3));
+    assert_eq!(cur.seek_forward_bounded(&4, 2), Some(true));
+    assert_eq!(cur.key(), Some(&4));
+    assert_eq!(cur.seek_forward_bounded(&4, 0), Some(true));
+    assert_eq!(cur.seek_forward_bounded(&1, 10), Some(false));
+    assert!(cur.is_null());
+  }
+
+  #[test]
+  fn test_remove_with_neighbors() {
+    let mut list: BTreeValueList<i32, ()> = (1..=3).collect();
+    let (prev, (k, _), next) = list.remove_with_neighbors(&2).unwrap();
+    assert_eq!((prev, k, next), (Some(1), 2, Some(3)));
+    let (prev, (k, _), next) = list.remove_with_neighbors(&1).unwrap();
+    assert_eq!((prev, k, next), (None, 1, Some(3)));
+    assert!(list.remove_with_neighbors(&1).is_none());
+    let (prev, (k, _), next) = list.remove_with_neighbors(&3).unwrap();
+    assert_eq!((prev, k, next), (None, 3, None));
+  }
+
+  #[cfg(feature = "rayon")]
+  #[test]
+  fn test_par_iter() {
+    use rayon::prelude::*;
+    let list: BTreeValueList<i32, i32> = (0..1000).map(|i| (i, i * 2)).collect();
+    let sum: i32 = list.par_nodes().map(|n| *n.value()).sum();
+    assert_eq!(sum, (0..1000).map(|i| i * 2).sum());
+    let keys: Vec<_> = list.par_iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, (0..1000).collect::<Vec<_>>());
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_auto_shrink() {
+    let mut list: KeyValueList<i32, ()> = (0..1000).collect();
+    assert_eq!(list.auto_shrink(), None);
+    list.pop_front_n(990);
+    let capacity = list.nodes.capacity();
+    assert!(capacity >= 1000);
+    list.set_auto_shrink(0.25);
+    assert_eq!(list.auto_shrink(), Some(0.25));
+    list.pop_front();
+    assert!(list.nodes.capacity() < capacity);
+    assert_eq!(
+      list.keys().copied().collect::<Vec<_>>(),
+      (991..1000).collect::<Vec<_>>()
+    );
+    list.disable_auto_shrink();
+    assert_eq!(list.auto_shrink(), None);
+    for threshold in [0.0, 1.0, -0.5, f32::NAN] {
+      let result =
+        std::panic::catch_unwind(|| KeyValueList::<i32, ()>::new().set_auto_shrink(threshold));
+      assert!(result.is_err());
+    }
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_auto_shrink_hysteresis() {
+    let mut list: KeyValueList<i32, ()> = (0..1000).collect();
+    list.set_auto_shrink(0.99);
+    list.pop_front();
+    let capacity = list.nodes.capacity();
+    assert!(capacity >= 1000);
+    // not shrunk again until the length halves, though the load factor
+    // stays below the threshold
+    while list.len() > 500 {
+      list.pop_front();
+      assert!(list.nodes.capacity() >= 900);
+    }
+    list.pop_front();
+    assert_eq!(list.len(), 499);
+    assert!(list.nodes.capacity() < 900);
+  }
+
+  #[test]
+  fn test_snapshot_order() {
+    let mut list: BTreeValueList<i32, ()> = (1..=4).collect();
+    let snapshot = list.snapshot_order();
+    assert_eq!(snapshot, [1, 2, 3, 4]);
+    let mut cur = list.cursor_mut(1);
+    cur.rotate_current_to_back();
+    cur.rotate_current_to_back();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [3, 4, 1, 2]);
+    list.reorder(&snapshot).unwrap();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    list.pop_back();
+    assert!(list.reorder(&snapshot).is_err());
+  }
+
+  #[cfg(feature = "metrics")]
+  #[test]
+  fn test_op_stats() {
+    let mut list: BTreeValueList<i32, ()> = (1..=3).collect();
+    let mut cur = list.cursor_mut(2);
+    cur.insert_after(4, ()).unwrap();
+    cur.rotate_current_to_back();
+    cur.remove_current();
+    list.pop_front();
+    assert_eq!(
+      list.op_stats(),
+      OpStats {
+        pushes: 3,
+        pops: 1,
+        inserts: 1,
+        removes: 1,
+        moves: 1,
+      }
+    );
+    list.reset_op_stats();
+    assert_eq!(list.op_stats(), OpStats::default());
+  }
+
+  #[cfg(feature = "metrics")]
+  #[test]
+  fn test_op_stats_bulk() {
+    let stats = |pushes, pops, moves| OpStats {
+      pushes,
+      pops,
+      moves,
+      ..OpStats::default()
+    };
+    let mut list: BTreeValueList<i32, ()> = (1..=5).collect();
+    let mut other: BTreeValueList<i32, ()> = (6..=7).collect();
+    list.reset_op_stats();
+    other.reset_op_stats();
+    // each moved pair is popped from the source and pushed to the target
+    list.append(&mut other).unwrap();
+    assert_eq!(list.op_stats(), stats(2, 0, 0));
+    assert_eq!(other.op_stats(), stats(0, 2, 0));
+    let rest = list.split_off(&4).unwrap();
+    assert_eq!(list.op_stats(), stats(2, 3, 0));
+    assert_eq!(rest.op_stats(), stats(3, 0, 0));
+    // reordering moves every pair
+    list.reorder(&[4, 3, 2, 1]).unwrap();
+    assert_eq!(list.op_stats(), stats(2, 3, 4));
+    // dropping or taking the whole map is not counted
+    list.take_map();
+    list.clear();
+    assert_eq!(list.op_stats(), stats(2, 3, 4));
+  }
+
+  #[test]
+  fn test_push_back_raw() {
+    let node = |prev, next| {
+      let mut n = ValueNode::new(());
+      *node_prev_mut!(n) = prev;
+      *node_next_mut!(n) = next;
+      n
+    };
+    let mut list = BTreeValueList::new();
+    list.push_back_raw(2, node(Some(1), Some(3))).unwrap();
+    list.push_back_raw(3, node(Some(2), None)).unwrap();
+    assert!(list.push_back_raw(3, node(None, None)).is_err());
+    assert_eq!(list.rebuild_from_links(), Err(LinkError::DanglingLink(2)));
+    list.push_back_raw(1, node(None, Some(3))).unwrap();
+    assert!(matches!(
+      list.rebuild_from_links(),
+      Err(LinkError::MismatchedLink(1 | 2))
+    ));
+    *node_next_mut!(list.node_mut(&1).unwrap()) = Some(2);
+    list.rebuild_from_links().unwrap();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(list.back_key(), Some(&3));
+    list.push_back_raw(4, node(None, None)).unwrap();
+    assert_eq!(list.rebuild_from_links(), Err(LinkError::MultipleFronts));
+    list.remove(&4);
+    list.push_back_raw(5, node(Some(6), Some(6))).unwrap();
+    list.push_back_raw(6, node(Some(5), Some(5))).unwrap();
+    assert_eq!(list.rebuild_from_links(), Err(LinkError::Unreachable));
+  }
+
+  #[test]
+  fn test_keys_diff() {
+    let a: BTreeValueList<i32, ()> = [4, 1, 3, 2].into_iter().collect();
+    let b: BTreeValueList<i32, ()> = [2, 5, 4].into_iter().collect();
+    assert_eq!(a.keys_not_in(&b).copied().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(a.keys_in_both(&b).copied().collect::<Vec<_>>(), [4, 2]);
+    assert_eq!(b.keys_not_in(&a).copied().collect::<Vec<_>>(), [5]);
+  }
+
+  #[test]
+  fn test_move_before_after() {
+    let mut list: BTreeValueList<i32, ()> = (1..=5).collect();
+    assert!(list.move_before(&5, &1));
+    assert_eq!(linked_keys(&list), [5, 1, 2, 3, 4]);
+    assert!(list.move_after(&5, &4));
+    assert_eq!(linked_keys(&list), [1, 2, 3, 4, 5]);
+    assert!(list.move_before(&2, &3));
+    assert_eq!(linked_keys(&list), [1, 2, 3, 4, 5]);
+    assert!(list.move_after(&2, &3));
+    assert_eq!(linked_keys(&list), [1, 3, 2, 4, 5]);
+    assert!(list.move_before(&2, &3));
+    assert_eq!(linked_keys(&list), [1, 2, 3, 4, 5]);
+    assert!(list.move_after(&1, &5));
+    assert_eq!(linked_keys(&list), [2, 3, 4, 5, 1]);
+    assert!(!list.move_before(&1, &1));
+    assert!(!list.move_after(&1, &6));
+    assert_eq!(list.front_key(), Some(&2));
+    assert_eq!(list.back_key(), Some(&1));
+  }
+
+  #[test]
+  fn test_map() {
+    let list: BTreeValueList<i32, i32> = [(3, 30), (1, 10), (2, 20)].into();
+    let mapped: BTreeValueList<String, i32> =
+      list.clone().map(|k, n| (k.to_string(), n.into_value() + 1));
+    assert_eq!(mapped.keys().cloned().collect::<Vec<_>>(), ["3", "1", "2"]);
+    assert_eq!(mapped["1"].value(), &11);
+    let mapped: BTreeValueList<i32, i32> = list.map(|k, n| (k % 2, *n.value()));
+    assert_eq!(mapped.keys().copied().collect::<Vec<_>>(), [1, 0]);
+    assert_eq!(mapped[&1].value(), &30);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_retain_and_shrink() {
+    let mut list: KeyValueList<i32, i32> = (0..1000).map(|i| (i, i)).collect();
+    let capacity = list.nodes.capacity();
+    list.retain_and_shrink(|k, _| k % 100 == 0);
+    assert_eq!(
+      list.keys().copied().collect::<Vec<_>>(),
+      (0..10).map(|i| i * 100).collect::<Vec<_>>()
+    );
+    assert!(list.nodes.capacity() < capacity);
+    assert_eq!(list.back_node().unwrap().next(), None);
+  }
+
+  #[test]
+  fn test_cursor_collecting() {
+    let mut list: BTreeValueList<i32, ()> = (1..=5).collect();
+    let mut cur = list.cursor_mut(2);
+    assert_eq!(cur.advance_collecting(2), [3, 4]);
+    assert_eq!(cur.key(), Some(&4));
+    assert_eq!(cur.advance_collecting(5), [5]);
+    assert!(cur.is_null());
+    assert_eq!(cur.retreat_collecting(2), [5, 4]);
+    assert_eq!(cur.retreat_collecting(0), []);
+    assert_eq!(cur.retreat_collecting(10), [3, 2, 1]);
+    assert!(cur.is_null());
+  }
+
+  #[test]
+  fn test_get_or_insert_default() {
+    let mut list = BTreeValueList::new();
+    for c in "abcab".chars() {
+      *list.get_or_insert_default_back(c) += 1;
+    }
+    *list.get_or_insert_default_front('z') += 1;
+    *list.get_or_insert_default_front('c') += 1;
+    assert_eq!(
+      list
+        .iter()
+        .map(|(k, n)| (*k, *n.value()))
+        .collect::<Vec<_>>(),
+      [('z', 1), ('a', 2), ('b', 2), ('c', 2)]
+    );
+  }
+
+  #[test]
+  fn test_to_indexed_pairs() {
+    let list: BTreeValueList<i32, &str> = [(2, "b"), (1, "a")].into();
+    assert_eq!(list.to_indexed_pairs(), [(0, &2, &"b"), (1, &1, &"a")]);
+  }
+
+  #[test]
+  fn test_cursor_remove_until() {
+    let mut list: BTreeValueList<i32, ()> = (1..=6).collect();
+    let mut cur = list.cursor_mut(2);
+    let removed = cur.remove_until(&4);
+    assert_eq!(cur.key(), Some(&4));
+    assert_eq!(removed.keys().copied().collect::<Vec<_>>(), [2, 3]);
+    assert!(cur.remove_until(&4).is_empty());
+    cur.move_next();
+    let removed = cur.remove_until(&1);
+    assert!(cur.is_null());
+    assert_eq!(removed.keys().copied().collect::<Vec<_>>(), [5, 6]);
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 4]);
+    assert_eq!(list.back_node().unwrap().next(), None);
+  }
+
+  #[test]
+  fn test_first_order_difference() {
+    let a: BTreeValueList<i32, i32> = [(1, 1), (2, 2), (3, 3)].into();
+    let mut b = a.clone();
+    assert_eq!(a.first_order_difference(&b), None);
+    *b.node_mut(&3).unwrap().value_mut() = 0;
+    assert_eq!(a.first_order_difference(&b), Some(2));
+    b.pop_back();
+    // the next key of node 2 differs
+    assert_eq!(a.first_order_difference(&b), Some(1));
+    assert_eq!(b.first_order_difference(&a), Some(1));
+    b.move_before(&2, &1);
+    assert_eq!(a.first_order_difference(&b), Some(0));
+  }
+
+  #[test]
+  fn test_cursor_into_consuming_iter() {
+    let mut list: BTreeValueList<i32, ()> = (1..=5).collect();
+    let mut iter = list.cursor_mut(2).into_consuming_iter();
+    assert_eq!(iter.next().map(|(k, _)| k), Some(2));
+    assert_eq!(iter.next().map(|(k, _)| k), Some(3));
+    drop(iter);
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 4, 5]);
+    let keys: Vec<_> = list
+      .cursor_mut(4)
+      .into_consuming_iter()
+      .map(|(k, _)| k)
+      .collect();
+    assert_eq!(keys, [4, 5]);
+    assert_eq!(list.back_key(), Some(&1));
+    assert_eq!(list.front_node().unwrap().next(), None);
+  }
+
+  #[test]
+  fn test_impl_node_accessors() {
+    struct MyNode<K> {
+      value: i32,
+      p: Option<K>,
+      n: Option<K>,
+    }
+    impl_node!(MyNode<K> { Key = K, prev = p, next = n, accessors });
+    impl<K> From<i32> for MyNode<K> {
+      fn from(value: i32) -> Self {
+        Self {
+          value,
+          p: None,
+          n: None,
+        }
+      }
+    }
+    let mut list: KeyNodeList<&str, MyNode<&str>, BTreeMap<_, _>> = KeyNodeList::new();
+    list.push_back("a", 1).unwrap();
+    list.push_back("b", 2).unwrap();
+    assert_eq!(list[&"a"].next_key(), Some(&"b"));
+    assert_eq!(list[&"b"].prev_key(), Some(&"a"));
+    assert_eq!(list[&"b"].next_key(), None);
+    assert_eq!(list[&"b"].value, 2);
+  }
+
+  #[test]
+  fn test_from_entries_in_order() {
+    let entries = [(2, ValueNode::new("b")), (1, ValueNode::new("a"))];
+    let list = BTreeValueList::from_entries_in_order(entries).unwrap();
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [2, 1]);
+    assert_eq!(list[&2].next(), Some(&1));
+    let entries = [
+      (1, ValueNode::new(1)),
+      (2, ValueNode::new(2)),
+      (1, ValueNode::new(3)),
+    ];
+    assert_eq!(
+      BTreeValueList::from_entries_in_order(entries).unwrap_err(),
+      DuplicateKey(1)
+    );
+  }
+
+  #[test]
+  fn test_unlink_all() {
+    let mut list: BTreeValueList<i32, i32> = (1..=4).map(|i| (i, i)).collect();
+    assert_eq!(list.linked_len(), 4);
+    list.set_current(Some(2));
+    list.unlink_all();
+    // the nodes are kept, but not linked
+    assert_eq!((list.len(), list.linked_len()), (4, 0));
+    assert_eq!((list.front_key(), list.back_key()), (None, None));
+    assert_eq!(list.current(), None);
+    assert_eq!(list.iter().len(), 0);
+    assert_eq!(list.iter().next(), None);
+    assert_eq!(list[&2].value(), &2);
+    assert!(list
+      .nodes
+      .values()
+      .all(|n| n.prev().is_none() && n.next().is_none()));
+    // unlinked pairs can be removed and moved back
+    assert_eq!(list.remove(&4).map(|(k, _)| k), Some(4));
+    list.move_to_back(&1);
+    assert_eq!((list.len(), list.linked_len()), (3, 1));
+    assert_eq!(linked_keys(&list), [1]);
+    list.push_front(5, 5).unwrap();
+    assert_eq!(linked_keys(&list), [5, 1]);
+    assert_eq!(list.iter().len(), 2);
+    // relinking all pairs
+    list.reorder(&[3, 2, 5, 1]).unwrap();
+    assert_eq!(linked_keys(&list), [3, 2, 5, 1]);
+    assert_eq!(list.linked_len(), 4);
+    list.unlink_all();
+    list.clear();
+    list.push_back(6, 6).unwrap();
+    assert_eq!((list.len(), list.linked_len()), (1, 1));
+  }
+
+  #[test]
+  fn test_str_lookup() {
+    let mut list: BTreeValueList<String, i32> = BTreeValueList::new();
+    list.push_back("abc".to_string(), 1).unwrap();
+    list.push_back("def".to_string(), 2).unwrap();
+    list.push_back("ghi".to_string(), 3).unwrap();
+    let key = "def".to_string();
+    assert_eq!(list.node("abc").map(|n| *n.value()), Some(1));
+    assert_eq!(list.node(&key).map(|n| *n.value()), Some(2));
+    assert!(list.contains_key("ghi"));
+    assert!(!list.contains_key("xyz"));
+    assert_eq!(list["ghi"].value(), &3);
+    assert!(list.cursor_at("xyz").is_null());
+    assert_eq!(list.cursor_at("def").key(), Some(&key));
+    let mut cur = list.cursor_at_mut("abc");
+    cur.move_next();
+    assert_eq!(cur.key().map(String::as_str), Some("def"));
+    assert_eq!(list.remove("def").map(|(_, n)| n.into_value()), Some(2));
+    assert_eq!(
+      list.keys().map(String::as_str).collect::<Vec<_>>(),
+      ["abc", "ghi"]
+    );
+  }
+
+  #[test]
+  fn test_node_pairs() {
+    let list: BTreeValueList<i32, i32> = BTreeValueList::from([(1, 10), (2, 13), (3, 19)]);
+    let deltas: Vec<_> = list
+      .node_pairs()
+      .map(|(a, b)| b.value() - a.value())
+      .collect();
+    assert_eq!(deltas, [3, 6]);
+    let single: BTreeValueList<i32, i32> = BTreeValueList::from([(1, 10)]);
+    assert_eq!(single.node_pairs().count(), 0);
+    let empty: BTreeValueList<i32, i32> = BTreeValueList::new();
+    assert_eq!(empty.node_pairs().count(), 0);
+  }
+
+  #[test]
+  fn test_replace_front_back() {
+    let mut list: BTreeValueList<i32, &str> = BTreeValueList::new();
+    assert!(list.replace_front("a").is_none());
+    assert!(list.replace_back("a").is_none());
+    list.extend([(1, "a"), (2, "b"), (3, "c")]);
+    assert_eq!(list.replace_front("x").map(|n| n.into_value()), Some("a"));
+    assert_eq!(list.replace_back("z").map(|n| n.into_value()), Some("c"));
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, "x"), (2, "b"), (3, "z")]);
+    // the replaced nodes keep their links
+    assert_eq!(list.node(&1).unwrap().next(), Some(&2));
+    assert_eq!(list.node(&3).unwrap().prev(), Some(&2));
+  }
+
+  #[test]
+  fn test_iter_from() {
+    let list: BTreeValueList<i32, i32> = (1..=5).map(|i| (i, i * 10)).collect();
+    let keys: Vec<_> = list.iter_from(&3).map(|(k, _)| *k).collect();
+    assert_eq!(keys, [3, 4, 5]);
+    let keys: Vec<_> = list.riter_from(&3).map(|(k, _)| *k).collect();
+    assert_eq!(keys, [3, 2, 1]);
+    assert_eq!(list.iter_from(&5).count(), 1);
+    assert_eq!(list.iter_from(&6).count(), 0);
+    assert_eq!(list.riter_from(&0).count(), 0);
+    // starting at the front and back keys
+    let keys: Vec<_> = list.iter_from(&1).map(|(k, _)| *k).collect();
+    assert_eq!(keys, [1, 2, 3, 4, 5]);
+    let keys: Vec<_> = list.riter_from(&5).map(|(k, _)| *k).collect();
+    assert_eq!(keys, [5, 4, 3, 2, 1]);
+    let mut iter = list.riter_from(&1);
+    assert_eq!(iter.size_hint(), (1, None));
+    assert_eq!(iter.next().map(|(k, _)| *k), Some(1));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    let mut iter = list.iter_from(&5);
+    assert_eq!(iter.size_hint(), (1, None));
+    assert_eq!(iter.next().map(|(k, _)| *k), Some(5));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    // the other end of a reversed iterator is the front of the list
+    let keys: Vec<_> = list.riter_from(&3).rev().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [1, 2, 3]);
+  }
+
+  #[test]
+  fn test_swap_adjacent() {
+    let mut list: BTreeValueList<i32, ()> = (1..=4).map(|i| (i, ())).collect();
+    // swapping the front pair updates the front key
+    assert!(list.swap_adjacent(&1));
+    assert_eq!(linked_keys(&list), [2, 1, 3, 4]);
+    assert_eq!(list.front_key(), Some(&2));
+    // swapping into the back updates the back key
+    assert!(list.swap_adjacent(&3));
+    assert_eq!(linked_keys(&list), [2, 1, 4, 3]);
+    assert_eq!(list.back_key(), Some(&3));
+    assert!(list.swap_adjacent(&1));
+    assert_eq!(linked_keys(&list), [2, 4, 1, 3]);
+    // the back key and missing keys leave the list unchanged
+    assert!(!list.swap_adjacent(&3));
+    assert!(!list.swap_adjacent(&5));
+    assert_eq!(linked_keys(&list), [2, 4, 1, 3]);
+  }
+
+  #[test]
+  fn test_current() {
+    let mut list: BTreeValueList<i32, ()> = (1..=3).map(|i| (i, ())).collect();
+    assert_eq!(list.current(), None);
+    list.advance_current();
+    assert_eq!(list.current(), Some(&1));
+    list.advance_current();
+    assert_eq!(list.current(), Some(&2));
+    list.push_back(4, ()).unwrap();
+    list.retreat_current();
+    assert_eq!(list.current(), Some(&1));
+    list.retreat_current();
+    assert_eq!(list.current(), None);
+    list.retreat_current();
+    assert_eq!(list.current(), Some(&4));
+    list.advance_current();
+    assert_eq!(list.current(), None);
+    list.set_current(Some(5));
+    assert_eq!(list.current(), None);
+    list.set_current(Some(3));
+    assert_eq!(list.current(), Some(&3));
+    list.remove(&2);
+    assert_eq!(list.current(), Some(&3));
+    list.remove(&3);
+    assert_eq!(list.current(), None);
+    list.set_current(Some(4));
+    list.clear();
+    assert_eq!(list.current(), None);
+  }
+
+  #[test]
+  fn test_remove_indexed() {
+    let mut list: BTreeValueList<i32, i32> = (1..=4).map(|i| (i, i * 10)).collect();
+    let (i, k, n) = list.remove_indexed(&3).unwrap();
+    assert_eq!((i, k, n.into_value()), (2, 3, 30));
+    assert_eq!(
+      list.remove_indexed(&1).map(|(i, k, _)| (i, k)),
+      Some((0, 1))
+    );
+    assert_eq!(
+      list.remove_indexed(&4).map(|(i, k, _)| (i, k)),
+      Some((1, 4))
+    );
+    assert!(list.remove_indexed(&5).is_none());
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [2]);
+  }
+
+  #[test]
+  fn test_from_iter_keeping() {
+    let events = [(1, 5), (2, 3), (1, 8), (3, 1), (2, 2), (1, 6)];
+    let max: BTreeValueList<i32, i32> = BTreeValueList::from_iter_keeping_max(events);
+    let vec: Vec<_> = max.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, 8), (2, 3), (3, 1)]);
+    let min: BTreeValueList<i32, i32> = BTreeValueList::from_iter_keeping_min(events);
+    let vec: Vec<_> = min.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, 5), (2, 2), (3, 1)]);
+  }
+
+  #[test]
+  fn test_split_half() {
+    let list: BTreeValueList<i32, ()> = (1..=5).map(|i| (i, ())).collect();
+    let (a, b) = list.split_half();
+    // the first half takes the middle pair
+    assert_eq!(linked_keys(&a), [1, 2, 3]);
+    assert_eq!(linked_keys(&b), [4, 5]);
+    assert_eq!((a.len(), b.len()), (3, 2));
+    let (c, d) = b.split_half();
+    assert_eq!((linked_keys(&c), linked_keys(&d)), (vec![4], vec![5]));
+    // a single pair stays in the first half
+    let (e, f) = c.split_half();
+    assert_eq!((linked_keys(&e), f.len()), (vec![4], 0));
+    let (g, h) = f.split_half();
+    assert!(g.is_empty() && h.is_empty());
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_unique_values() {
+    let mut list: KeyValueList<i32, &str> =
+      KeyValueList::from([(1, "a"), (2, "b"), (3, "a"), (4, "c"), (5, "b")]);
+    assert!(!list.has_unique_values());
+    assert_eq!(list.remove_duplicate_values(), 2);
+    assert!(list.has_unique_values());
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, "a"), (2, "b"), (4, "c")]);
+    assert_eq!(list.remove_duplicate_values(), 0);
+  }
+
+  #[test]
+  fn test_step_by_keys() {
+    let list: BTreeValueList<i32, ()> = (1..=7).map(|i| (i, ())).collect();
+    let keys: Vec<_> = list.step_by_keys(3).copied().collect();
+    assert_eq!(keys, [1, 4, 7]);
+    assert_eq!(list.step_by_keys(1).count(), 7);
+    assert_eq!(list.step_by_keys(10).count(), 1);
+    #[cfg(feature = "std")]
+    {
+      let result = std::panic::catch_unwind(|| list.step_by_keys(0).count());
+      assert!(result.is_err());
+    }
+  }
+
+  #[test]
+  fn test_take_restore_map() {
+    let mut list: BTreeValueList<i32, i32> = (1..=3).map(|i| (i, i * 10)).collect();
+    let mut map = list.take_map();
+    assert!(list.is_empty());
+    assert_eq!((list.front_key(), list.back_key()), (None, None));
+    assert_eq!(map.len(), 3);
+    *map.get_mut(&2).unwrap().value_mut() = 25;
+    list.restore_map(map).unwrap();
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, 10), (2, 25), (3, 30)]);
+    let mut map = list.take_map();
+    map.remove(&2);
+    let (err, map) = list.restore_map(map).unwrap_err();
+    assert!(matches!(err, LinkError::DanglingLink(1 | 3)));
+    assert_eq!(map.len(), 2);
+    assert!(list.is_empty());
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_reset() {
+    let mut list: KeyValueList<i32, ()> = KeyValueList::new();
+    for round in 0..3 {
+      list.extend((0..100).map(|i| (i, ())));
+      let capacity = list.nodes.capacity();
+      list.reset();
+      assert!(list.is_empty(), "round {round}");
+      assert!(list.nodes.capacity() >= capacity, "round {round}");
+    }
+  }
+
+  #[test]
+  fn test_with_node_links() {
+    let mut list: BTreeValueList<i32, ()> = (1..=4).map(|i| (i, ())).collect();
+    // move 1 between 3 and 4
+    let r = list.with_node_links(&1, |h| {
+      assert_eq!((h.prev(), h.next()), (None, Some(&2)));
+      h.set_prev(Some(3));
+      h.set_next(Some(4));
+      42
+    });
+    assert_eq!(r, Some(Ok(42)));
+    assert_eq!(linked_keys(&list), [2, 3, 1, 4]);
+    assert_eq!(list.front_key(), Some(&2));
+    // move 2 to the back
+    let r = list.with_node_links(&2, |h| {
+      h.set_prev(Some(4));
+      h.set_next(None);
+    });
+    assert_eq!(r, Some(Ok(())));
+    assert_eq!(linked_keys(&list), [3, 1, 4, 2]);
+    assert_eq!(list.back_key(), Some(&2));
+    // unchanged links
+    assert_eq!(list.with_node_links(&4, |_| ()), Some(Ok(())));
+    // invalid proposals are rejected, and the links of the node and its
+    // neighbors are rolled back
+    let r = list.with_node_links(&4, |h| h.set_prev(Some(3)));
+    assert_eq!(r, Some(Err(LinkError::MismatchedLink(4))));
+    let r = list.with_node_links(&4, |h| h.set_next(Some(4)));
+    assert_eq!(r, Some(Err(LinkError::MismatchedLink(4))));
+    let r = list.with_node_links(&4, |h| h.set_prev(Some(9)));
+    assert_eq!(r, Some(Err(LinkError::DanglingLink(9))));
+    assert!(list.with_node_links(&9, |_| ()).is_none());
+    let node = list.node(&4).unwrap();
+    assert_eq!((node.prev(), node.next()), (Some(&1), Some(&2)));
+    assert_eq!(list.node(&1).unwrap().next(), Some(&4));
+    assert_eq!(list.node(&2).unwrap().prev(), Some(&4));
+    assert_eq!(linked_keys(&list), [3, 1, 4, 2]);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_linked_list() {
+    use std::collections::LinkedList;
+    let list: KeyValueList<i32, &str> = KeyValueList::from([(2, "b"), (1, "a"), (3, "c")]);
+    let ll = list.into_linked_list();
+    assert_eq!(
+      ll.iter().copied().collect::<Vec<_>>(),
+      [(2, "b"), (1, "a"), (3, "c")]
+    );
+    let list: KeyValueList<i32, &str> = KeyValueList::from_linked_list(ll).unwrap();
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(2, "b"), (1, "a"), (3, "c")]);
+    let ll = LinkedList::from([(1, "a"), (2, "b"), (1, "c")]);
+    let err = KeyValueList::<i32, &str>::from_linked_list(ll).unwrap_err();
+    assert_eq!(err, DuplicateKey(1));
+  }
+
+  #[test]
+  fn test_max_min_by_value() {
+    let list: BTreeValueList<&str, i32> =
+      BTreeValueList::from([("a", 3), ("b", 7), ("c", 1), ("d", 7), ("e", 1)]);
+    assert_eq!(list.max_by_value(), Some((&"b", &7)));
+    assert_eq!(list.min_by_value(), Some((&"c", &1)));
+    let by_rev = |a: &i32, b: &i32| b.cmp(a);
+    assert_eq!(list.max_by(by_rev), Some((&"c", &1)));
+    assert_eq!(list.min_by(by_rev), Some((&"b", &7)));
+    let empty: BTreeValueList<&str, i32> = BTreeValueList::new();
+    assert_eq!(empty.max_by_value(), None);
+    assert_eq!(empty.min_by_value(), None);
+  }
+
+  #[test]
+  fn test_insert_all_after_key() {
+    let mut list: BTreeValueList<i32, ()> = BTreeValueList::from([(1, ()), (2, ())]);
+    list.insert_all_after_key(&1, [(10, ()), (11, ())]).unwrap();
+    assert_eq!(linked_keys(&list), [1, 10, 11, 2]);
+    list.insert_all_after_key(&2, [(20, ())]).unwrap();
+    assert_eq!(list.back_key(), Some(&20));
+    // a missing anchor inserts nothing
+    let err = list.insert_all_after_key(&3, [(30, ())]).unwrap_err();
+    assert_eq!(err, InsertError::MissingAnchor);
+    assert!(!list.contains_key(&30));
+    assert_eq!(list.len(), 5);
+    // a duplicate key stops the insertion, the pairs before it are kept
+    // and the pairs after it are not inserted
+    let err = list
+      .insert_all_after_key(&10, [(12, ()), (1, ()), (13, ())])
+      .unwrap_err();
+    assert_eq!(
+      err,
+      InsertError::DuplicateKey {
+        index: 1,
+        key: 1,
+        node: ()
+      }
+    );
+    assert_eq!(linked_keys(&list), [1, 10, 12, 11, 2, 20]);
+    assert!(!list.contains_key(&13));
+    assert_eq!(list.front_key(), Some(&1));
+  }
+
+  #[test]
+  fn test_group_runs() {
+    let list: BTreeValueList<i32, i32> =
+      BTreeValueList::from([(1, 5), (2, 5), (3, 7), (4, 5), (5, 15), (6, 25)]);
+    let runs: Vec<_> = list.group_runs().collect();
+    assert_eq!(
+      runs,
+      [
+        (&5, vec![&1, &2]),
+        (&7, vec![&3]),
+        (&5, vec![&4]),
+        (&15, vec![&5]),
+        (&25, vec![&6]),
+      ]
+    );
+    let runs: Vec<_> = list.group_runs_by(|v| v % 10).collect();
+    assert_eq!(
+      runs,
+      [(&5, vec![&1, &2]), (&7, vec![&3]), (&5, vec![&4, &5, &6])]
+    );
+    let empty: BTreeValueList<i32, i32> = BTreeValueList::new();
+    assert_eq!(empty.group_runs().count(), 0);
+  }
+
+  #[test]
+  fn test_find() {
+    let list: BTreeValueList<i32, &str> = BTreeValueList::from([(3, "c"), (1, "a"), (2, "b")]);
+    assert_eq!(list.find(&3).map(|(i, n)| (i, *n.value())), Some((0, "c")));
+    assert_eq!(list.find(&2).map(|(i, n)| (i, *n.value())), Some((2, "b")));
+    assert!(list.find(&4).is_none());
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn test_key_order() {
+    use serde_test::{assert_ser_tokens, Token};
+    let list: BTreeValueList<i32, &str> = BTreeValueList::from([(3, "c"), (1, "a"), (2, "b")]);
+    assert_ser_tokens(
+      &list.key_order(),
+      &[
+        Token::Seq { len: Some(3) },
+        Token::I32(3),
+        Token::I32(1),
+        Token::I32(2),
+        Token::SeqEnd,
+      ],
+    );
+    let order: Vec<i32> = list.key_order().keys().iter().map(|k| **k).collect();
+    let map: BTreeMap<_, _> = list
+      .iter()
+      .map(|(k, n)| (*k, (*n.value()).into()))
+      .collect();
+    let list2: BTreeValueList<i32, &str> =
+      BTreeValueList::from_map_with_order(map, &order).unwrap();
+    assert!(list.values_eq(&list2));
+    let err =
+      BTreeValueList::<i32, &str>::from_map_with_order(BTreeMap::new(), &order).unwrap_err();
+    assert_eq!(
+      err,
+      ReorderError::LengthMismatch {
+        expected: 0,
+        found: 3
+      }
+    );
+  }
+
+  #[test]
+  fn test_rotate_values_left() {
+    let mut list: BTreeValueList<&str, i32> = BTreeValueList::from([("a", 1), ("b", 2), ("c", 3)]);
+    let values = |l: &BTreeValueList<&str, i32>| l.nodes().map(|n| *n.value()).collect::<Vec<_>>();
+    list.rotate_values_left(1);
+    assert_eq!(values(&list), [2, 3, 1]);
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), ["a", "b", "c"]);
+    list.rotate_values_left(5);
+    assert_eq!(values(&list), [1, 2, 3]);
+    list.rotate_values_left(3);
+    assert_eq!(values(&list), [1, 2, 3]);
+    let mut empty: BTreeValueList<&str, i32> = BTreeValueList::new();
+    empty.rotate_values_left(2);
+    assert!(empty.is_empty());
+  }
+
+  #[test]
+  fn test_coalesce() {
+    // merge adjacent intervals that overlap or touch
+    let mut list: BTreeValueList<i32, (i32, i32)> = BTreeValueList::from([
+      (1, (0, 2)),
+      (2, (2, 4)),
+      (3, (3, 5)),
+      (4, (7, 8)),
+      (5, (8, 9)),
+    ]);
+    list.coalesce(|_, a, _, b| (a.1 >= b.0).then_some((a.0, a.1.max(b.1))));
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, (0, 5)), (4, (7, 9))]);
+    assert_eq!(list.back_key(), Some(&4));
+    assert_eq!(list.back_node().and_then(|n| n.next()), None);
+    list.coalesce(|_, _, _, _| None);
+    assert_eq!(list.len(), 2);
+    list.coalesce(|_, a, _, b| Some((a.0, b.1)));
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(1, (0, 9))]);
+    assert_eq!((list.front_key(), list.back_key()), (Some(&1), Some(&1)));
+  }
+
+  #[test]
+  fn test_append() {
+    let mut a: BTreeValueList<i32, ()> = (1..=3).map(|i| (i, ())).collect();
+    let mut b: BTreeValueList<i32, ()> = (4..=5).map(|i| (i, ())).collect();
+    a.append(&mut b).unwrap();
+    assert_eq!(linked_keys(&a), [1, 2, 3, 4, 5]);
+    assert!(b.is_empty() && b.front_key().is_none() && b.back_key().is_none());
+    // collisions are reported in the order of `other`, and nothing moves
+    let mut c: BTreeValueList<i32, ()> = [6, 2, 7, 5].into_iter().map(|i| (i, ())).collect();
+    assert_eq!(a.append(&mut c).unwrap_err(), KeyCollision(vec![2, 5]));
+    assert_eq!(linked_keys(&a), [1, 2, 3, 4, 5]);
+    assert_eq!(linked_keys(&c), [6, 2, 7, 5]);
+    assert!(!a.contains_key(&6) && !a.contains_key(&7));
+    // append to an empty list, and append an empty list
+    let mut d = BTreeValueList::new();
+    d.append(&mut a).unwrap();
+    d.append(&mut b).unwrap();
+    assert_eq!(linked_keys(&d), [1, 2, 3, 4, 5]);
+    // the emptied list can be reused
+    b.push_back(9, ()).unwrap();
+    assert_eq!(linked_keys(&b), [9]);
+  }
+
+  #[test]
+  fn test_split_off() {
+    let mut list: BTreeValueList<i32, ()> = (1..=5).map(|i| (i, ())).collect();
+    let rest = list.split_off(&2).unwrap();
+    assert_eq!(linked_keys(&list), [1, 2]);
+    assert_eq!(linked_keys(&rest), [3, 4, 5]);
+    assert_eq!((list.len(), rest.len()), (2, 3));
+    assert!(!list.contains_key(&3) && !rest.contains_key(&2));
+    // splitting at the back key leaves the list unchanged
+    let empty = list.split_off(&2).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!((empty.front_key(), empty.back_key()), (None, None));
+    assert!(list.split_off(&9).is_none());
+    assert_eq!(linked_keys(&list), [1, 2]);
+  }
+
+  #[test]
+  fn test_double_ended_iter() {
+    let list: BTreeValueList<i32, i32> = (1..=5).map(|i| (i, i * 10)).collect();
+    let keys: Vec<_> = list.keys().rev().copied().collect();
+    assert_eq!(keys, [5, 4, 3, 2, 1]);
+    let values: Vec<_> = list.nodes().rev().map(|n| *n.value()).collect();
+    assert_eq!(values, [50, 40, 30, 20, 10]);
+    let mut iter = list.keys();
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    let mut iter = list.iter();
+    for _ in 0..2 {
+      iter.next();
+      iter.next_back();
+    }
+    assert_eq!(iter.next_back().map(|(k, _)| *k), Some(3));
+    assert!(iter.next().is_none());
+    let keys: Vec<_> = list.iter_from(&3).rev().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [5, 4, 3]);
+    assert_eq!(list.iter_from(&9).next_back(), None);
+    let empty: BTreeValueList<i32, i32> = BTreeValueList::new();
+    assert_eq!(empty.iter().next_back(), None);
+  }
+
+  #[test]
+  fn test_exact_size_iter() {
+    let list: BTreeValueList<i32, i32> = (1..=4).map(|i| (i, i)).collect();
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 4);
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    iter.next();
+    iter.next();
+    assert_eq!(iter.len(), 0);
+    assert_eq!(list.keys().len(), 4);
+    assert_eq!(list.nodes().skip(1).len(), 3);
+    let mut iter = list.clone().into_iter();
+    iter.next();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(list.clone().into_keys().len(), 4);
+    assert_eq!(list.clone().into_nodes().len(), 4);
+    assert_eq!(list.clone().into_values().len(), 4);
+    let iter = list.iter_from(&3);
+    assert_eq!(iter.size_hint(), (1, None));
+    assert_eq!(iter.count(), 2);
+    assert_eq!(list.iter_from(&9).size_hint(), (0, Some(0)));
+    let mut list = list;
+    list.unlink_all();
+    assert_eq!(list.iter().len(), list.iter().count());
+    assert_eq!(list.iter().len(), 0);
+    assert_eq!(list.clone().into_iter().len(), 0);
+    assert_eq!(list.clone().into_values().len(), 0);
+    list.push_back(5, 5).unwrap();
+    assert_eq!(list.iter().len(), 1);
+    assert_eq!(list.iter_from(&5).size_hint(), (1, None));
+    assert_eq!(list.into_keys().len(), 1);
+  }
+
+  #[test]
+  fn test_retain() {
+    let mut list: BTreeValueList<i32, i32> = (1..=6).map(|i| (i, i * 10)).collect();
+    let mut visited = Vec::new();
+    list.retain(|k, n| {
+      visited.push(*k);
+      *k != 1 && *k != 6 && *n.value() != 30
+    });
+    // every pair is visited once in list order, even after the front and
+    // its successors have been removed
+    assert_eq!(visited, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(linked_keys(&list), [2, 4, 5]);
+    assert_eq!(list.len(), 3);
+    list.retain(|_, _| false);
+    assert!(list.is_empty());
+    assert_eq!((list.front_key(), list.back_key()), (None, None));
+  }
+
+  #[test]
+  fn test_extract_if() {
+    let mut list: BTreeValueList<i32, ()> = (1..=8).map(|i| (i, ())).collect();
+    let evens: BTreeValueList<i32, ()> = list.extract_if(|k, _| k % 2 == 0).collect();
+    assert_eq!(linked_keys(&evens), [2, 4, 6, 8]);
+    assert_eq!(linked_keys(&list), [1, 3, 5, 7]);
+    // dropping the iterator early keeps the pairs not yet visited
+    {
+      let mut iter = list.extract_if(|k, _| *k > 1);
+      assert_eq!(iter.next().map(|(k, _)| k), Some(3));
+    }
+    assert_eq!(linked_keys(&list), [1, 5, 7]);
+    assert_eq!(list.len(), 3);
+    assert!(!list.contains_key(&3));
+  }
+
+  #[test]
+  fn test_iter_mut() {
+    let mut list: BTreeValueList<i32, i32> = BTreeValueList::from([(3, 1), (1, 2), (2, 3)]);
+    let mut visited = Vec::new();
+    for (k, n) in list.iter_mut() {
+      visited.push(*k);
+      *n.value_mut() *= 10;
+    }
+    assert_eq!(visited, [3, 1, 2]);
+    for n in list.nodes_mut() {
+      *n.value_mut() += 1;
+    }
+    assert_eq!(list.nodes_mut().len(), 3);
+    let last = list.nodes_mut().next_back().map(|n| *n.value());
+    assert_eq!(last, Some(31));
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(3, 11), (1, 21), (2, 31)]);
+  }
+
+  #[test]
+  fn test_get_or_insert() {
+    let mut list: BTreeValueList<i32, Vec<i32>> = BTreeValueList::new();
+    list.get_or_insert_back(1, vec![]).value_mut().push(10);
+    list.get_or_insert_back(2, vec![]).value_mut().push(20);
+    list.get_or_insert_back(1, vec![99]).value_mut().push(11);
+    list.get_or_insert_front(3, vec![30]);
+    list.get_or_insert_front(2, vec![]).value_mut().push(21);
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, n.value().clone())).collect();
+    assert_eq!(vec, [(3, vec![30]), (1, vec![10, 11]), (2, vec![20, 21])]);
+
+    // the key is not cloned if it already exists
+    // the flag tells whether cloning is allowed, and is not compared
+    #[derive(Debug)]
+    struct Key(i32, bool);
+    impl PartialEq for Key {
+      fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+      }
+    }
+    impl Eq for Key {}
+    impl PartialOrd for Key {
+      fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+      }
+    }
+    impl Ord for Key {
+      fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.cmp(&other.0)
+      }
+    }
+    impl core::hash::Hash for Key {
+      fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+      }
+    }
+    impl Clone for Key {
+      fn clone(&self) -> Self {
+        assert!(self.1, "key cloned on the hit path");
+        Key(self.0, self.1)
+      }
+    }
+    let mut list: BTreeValueList<Key, i32> = BTreeValueList::new();
+    list.get_or_insert_back(Key(1, true), 10);
+    *list.get_or_insert_back(Key(1, false), 0).value_mut() += 1;
+    *list.get_or_insert_front(Key(1, false), 0).value_mut() += 1;
+    assert_eq!(list[&Key(1, true)].value(), &12);
+  }
+
+  #[test]
+  fn test_entry() {
+    let mut list: BTreeValueList<i32, i32> = BTreeValueList::from([(1, 10), (2, 20)]);
+    *list.entry(3).or_insert_back(30).value_mut() += 1;
+    *list.entry(0).or_insert_front(0).value_mut() += 1;
+    assert_eq!(linked_keys(&list), [0, 1, 2, 3]);
+    // an existing key is neither moved nor overwritten
+    assert_eq!(list.entry(1).or_insert_back(99).value(), &10);
+    assert_eq!(list.entry(3).or_insert_front(99).value(), &31);
+    assert_eq!(linked_keys(&list), [0, 1, 2, 3]);
+    list
+      .entry(1)
+      .and_modify(|n| *n.value_mut() += 1)
+      .or_insert_back(99);
+    assert_eq!(list.entry(2).key(), &2);
+    match list.entry(4) {
+      Entry::Vacant(e) => assert_eq!(e.into_key(), 4),
+      Entry::Occupied(_) => unreachable!(),
+    }
+    assert!(!list.contains_key(&4));
+    match list.entry(2) {
+      Entry::Occupied(mut e) => {
+        assert_eq!(e.get().value(), &20);
+        *e.get_mut().value_mut() = 21;
+        assert_eq!(e.remove().1.into_value(), 21);
+      }
+      Entry::Vacant(_) => unreachable!(),
+    }
+    let vec: Vec<_> = list.iter().map(|(k, n)| (*k, *n.value())).collect();
+    assert_eq!(vec, [(0, 1), (1, 11), (3, 31)]);
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn test_serde() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+    let list: BTreeValueList<i32, &str> = BTreeValueList::from([(2, "b"), (1, "a")]);
+    assert_tokens(
+      &list,
+      &[
+        Token::Seq { len: Some(2) },
+        Token::Tuple { len: 2 },
+        Token::I32(2),
+        Token::BorrowedStr("b"),
+        Token::TupleEnd,
+        Token::Tuple { len: 2 },
+        Token::I32(1),
+        Token::BorrowedStr("a"),
+        Token::TupleEnd,
+        Token::SeqEnd,
+      ],
+    );
+    assert_de_tokens_error::<BTreeValueList<i32, &str>>(
+      &[
+        Token::Seq { len: Some(2) },
+        Token::Tuple { len: 2 },
+        Token::I32(1),
+        Token::BorrowedStr("a"),
+        Token::TupleEnd,
+        Token::Tuple { len: 2 },
+        Token::I32(1),
+        Token::BorrowedStr("b"),
+        Token::TupleEnd,
+      ],
+      "duplicate key at index 1",
+    );
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_with_capacity() {
+    let mut list: KeyValueList<i32, i32> = KeyValueList::with_capacity(100);
+    assert!(list.is_empty());
+    assert!(list.capacity() >= 100);
+    list.extend((0..100).map(|i| (i, i)));
+    list.reserve(1000);
+    assert!(list.capacity() >= 1100);
+    assert_eq!(list.len(), 100);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_shrink_to_fit() {
+    let mut list: KeyValueList<i32, i32> = (0..1000).map(|i| (i, i)).collect();
+    let capacity = list.capacity();
+    while list.len() > 10 {
+      list.pop_back();
+    }
+    list.shrink_to_fit();
+    assert!(list.capacity() < capacity);
+    assert_eq!(
+      list.keys().copied().collect::<Vec<_>>(),
+      (0..10).collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn test_move_to_front_back() {
+    let mut list: BTreeValueList<i32, ()> = [1, 2, 3, 4].into_iter().collect();
+    assert!(list.move_to_back(&1));
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [2, 3, 4, 1]);
+    assert!(list.move_to_front(&1));
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert!(list.move_to_back(&3));
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [1, 2, 4, 3]);
+    assert!(list.move_to_front(&4));
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [4, 1, 2, 3]);
+    assert!(list.move_to_back(&3));
+    assert!(list.move_to_front(&4));
+    assert_eq!(list.keys().rev().copied().collect::<Vec<_>>(), [3, 2, 1, 4]);
+    assert!(!list.move_to_back(&5));
+    assert!(!list.move_to_front(&5));
+    let mut list: BTreeValueList<i32, ()> = [1].into_iter().collect();
+    assert!(list.move_to_back(&1));
+    assert!(list.move_to_front(&1));
+    assert_eq!(list.front_key(), Some(&1));
+    assert_eq!(list.back_key(), Some(&1));
+    assert_eq!(list.len(), 1);
+  }
+
+  #[test]
+  #[cfg(feature = "cache")]
+  fn test_lru_cache() {
+    let mut cache = LruCache::new(2);
+    assert_eq!(cache.put(1, "a"), None);
+    assert_eq!(cache.put(2, "b"), None);
+    assert_eq!(cache.get(&1), Some(&"a"));
+    assert_eq!(cache.put(3, "c"), Some((2, "b")));
+    assert_eq!(cache.peek(&1), Some(&"a"));
+    assert_eq!(cache.put(1, "d"), None);
+    assert_eq!(cache.as_list().keys().copied().collect::<Vec<_>>(), [3, 1]);
+    assert_eq!(cache.put(4, "e"), Some((3, "c")));
+    assert_eq!(cache.get(&1), Some(&"d"));
+    assert_eq!(cache.get(&3), None);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.pop_lru(), Some((4, "e")));
+    assert_eq!(cache.remove(&1), Some((1, "d")));
+    assert!(cache.is_empty());
+  }
+
+  #[test]
+  fn test_btree_value_list() {
+    let mut list = BTreeValueList::new();
+    list.push_back(3, "c").unwrap();
+    list.push_back(1, "a").unwrap();
+    list.push_front(2, "b").unwrap();
+    assert_eq!(list.push_back(1, "d").map_err(|(k, _)| k), Err(1));
+    assert_eq!(list[&1].value(), &"a");
+    assert_eq!(list.keys().copied().collect::<Vec<_>>(), [2, 3, 1]);
+    assert_eq!(list.remove(&3).map(|(k, _)| k), Some(3));
+    assert_eq!(list.front_node().unwrap().next(), Some(&1));
+    assert_eq!(list.back_node().unwrap().prev(), Some(&2));
+    list.push_back(0, "z").unwrap();
+    let map = list.take_map();
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), [0, 1, 2]);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn test_hash_eq_key() {
+    // a key type that implements `Hash` and `Eq`, but not `Ord`
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct Name(String);
+
+    impl core::borrow::Borrow<str> for Name {
+      fn borrow(&self) -> &str {
+        &self.0
+      }
+    }
+
+    let name = |s: &str| Name(s.into());
+    let mut list: KeyValueList<Name, i32> = [(name("a"), 1), (name("b"), 2)].into();
+    list.push_front(name("c"), 3).unwrap();
+    assert_eq!(list["a"].value(), &1);
+    assert!(list.contains_key("b"));
+    *list.entry(name("b")).or_insert_back(0).value_mut() += 10;
+    for (_, node) in list.iter_mut_rev() {
+      *node.value_mut() *= 2;
+    }
+    let mut cursor = list.cursor_mut(name("a"));
+    cursor.move_next();
+    assert_eq!(cursor.key(), Some(&name("b")));
+    assert!(list.unreachable_keys().is_empty());
+    assert_eq!(list.reindex().is_before(&list, "c", "b"), Some(true));
+    assert_eq!(list.remove("a").map(|(_, n)| n.into_value()), Some(2));
+    let vec: Vec<_> = list
+      .iter()
+      .map(|(k, n)| (k.0.as_str(), *n.value()))
+      .collect();
+    assert_eq!(vec, [("c", 6), ("b", 24)]);
+  }
 }
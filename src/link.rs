@@ -0,0 +1,141 @@
+use crate::error::LinkError;
+use crate::list::{KeyNodeList, Op};
+use crate::map::{Map, MapLookup};
+use crate::node::Node;
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+/// A handle to the links of a node, passed to the closure of
+/// [`KeyNodeList::with_node_links`].
+///
+/// The handle holds the current previous and next keys of the node, and
+/// new links proposed by the closure. The proposed links are validated and
+/// applied by the list after the closure returns.
+#[derive(Debug, Clone)]
+pub struct LinkHandle<K> {
+  key: K,
+  prev: Option<K>,
+  next: Option<K>,
+  new_prev: Option<K>,
+  new_next: Option<K>,
+}
+
+impl<K> LinkHandle<K> {
+  /// Returns a reference to the key of the node.
+  #[inline]
+  pub fn key(&self) -> &K {
+    &self.key
+  }
+
+  /// Returns a reference to the current previous key of the node.
+  #[inline]
+  pub fn prev(&self) -> Option<&K> {
+    self.prev.as_ref()
+  }
+
+  /// Returns a reference to the current next key of the node.
+  #[inline]
+  pub fn next(&self) -> Option<&K> {
+    self.next.as_ref()
+  }
+
+  /// Returns a reference to the proposed previous key of the node.
+  #[inline]
+  pub fn proposed_prev(&self) -> Option<&K> {
+    self.new_prev.as_ref()
+  }
+
+  /// Returns a reference to the proposed next key of the node.
+  #[inline]
+  pub fn proposed_next(&self) -> Option<&K> {
+    self.new_next.as_ref()
+  }
+
+  /// Proposes a new previous key of the node, `None` stands for the front
+  /// of the list.
+  #[inline]
+  pub fn set_prev(&mut self, prev: Option<K>) {
+    self.new_prev = prev;
+  }
+
+  /// Proposes a new next key of the node, `None` stands for the back
+  /// of the list.
+  #[inline]
+  pub fn set_next(&mut self, next: Option<K>) {
+    self.new_next = next;
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Calls `f` with a [`LinkHandle`] of the node at `key`, then validates
+  /// the links proposed by `f` and applies them, updating the neighbors
+  /// accordingly. Returns `None` if key does not exist.
+  ///
+  /// The proposed links are valid if they are the current links, or if the
+  /// proposed previous and next pairs are adjacent once the node is
+  /// detached, in which case the node is moved between them. Otherwise,
+  /// returns a [`LinkError`] and the list is unchanged.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn with_node_links<Q, F, R>(&mut self, key: &Q, f: F) -> Option<Result<R, LinkError<K>>>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+    F: FnOnce(&mut LinkHandle<K>) -> R,
+  {
+    let (key, node) = self.get_key_node(key)?;
+    let (prev, next) = (node.prev().cloned(), node.next().cloned());
+    let mut handle = LinkHandle {
+      key: key.clone(),
+      new_prev: prev.clone(),
+      new_next: next.clone(),
+      prev,
+      next,
+    };
+    let ret = f(&mut handle);
+    Some(self.apply_links(handle).map(|_| ret))
+  }
+
+  /// Validates and applies the proposed links of the handle.
+  fn apply_links(&mut self, handle: LinkHandle<K>) -> Result<(), LinkError<K>> {
+    let LinkHandle {
+      key,
+      prev,
+      next,
+      new_prev,
+      new_next,
+    } = handle;
+    if new_prev == prev && new_next == next {
+      return Ok(());
+    }
+    if new_prev.as_ref() == Some(&key) || new_next.as_ref() == Some(&key) {
+      return Err(LinkError::MismatchedLink(key));
+    }
+    // the next key of the proposed previous pair, once the node is detached
+    let after_prev = match &new_prev {
+      Some(p) => match self.node(p) {
+        Some(n) => n.next(),
+        None => return Err(LinkError::DanglingLink(p.clone())),
+      },
+      None => self.head.as_ref(),
+    };
+    let after_prev = if after_prev == Some(&key) {
+      next.as_ref()
+    } else {
+      after_prev
+    };
+    if after_prev != new_next.as_ref() {
+      return Err(LinkError::MismatchedLink(key));
+    }
+    self.unlink(&key);
+    self.link_between(&key, new_prev, new_next);
+    self.record_op(Op::Move);
+    Ok(())
+  }
+}
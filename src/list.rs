@@ -1,25 +1,65 @@
 use crate::cursor::{Cursor, CursorMut};
-use crate::iter::{IntoIter, IntoKeys, IntoNodes, Iter, Keys, Nodes};
-use crate::map::Map;
-use crate::node::Node;
+use crate::error::{
+  DuplicateKey, ExtendError, InsertError, KeyCollision, LinkError, MissingKey, ReorderError,
+};
+use crate::iter::{
+  ExtractIf, IntoIter, IntoKeys, IntoNodes, IntoValues, Iter, IterFrom, IterMut, Keys, Nodes,
+  NodesMut,
+};
+use crate::map::{IndexKey, KeyIndex, Map, MapCapacity, MapIter, MapLookup};
+#[cfg(feature = "metrics")]
+use crate::metrics::OpStats;
+use crate::node::{Node, ValueNode};
 use crate::{node_next_mut, node_prev_mut};
-use std::borrow::Borrow;
-use std::collections::HashMap;
-use std::fmt;
-use std::hash::Hash;
-use std::iter::FromIterator;
-use std::marker::PhantomData;
-use std::ops::Index;
+use alloc::borrow::ToOwned;
+use alloc::collections::TryReserveError;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::Hash;
+use core::iter::{self, FromIterator, Rev};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Index;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet, LinkedList};
 
 /// A doubly-linked list that stores key-node pairs.
+///
+/// The underlying hash map `M` defaults to [`HashMap`] when the `std`
+/// feature is enabled. Otherwise, it must be specified explicitly.
 #[derive(Clone)]
-pub struct KeyNodeList<K, N, M = HashMap<K, N>> {
+pub struct KeyNodeList<
+  K,
+  N,
+  #[cfg(feature = "std")] M = HashMap<K, N>,
+  #[cfg(not(feature = "std"))] M,
+> {
   pub(crate) nodes: M,
   pub(crate) head: Option<K>,
   pub(crate) tail: Option<K>,
+  current: Option<K>,
+  unlinked: usize,
+  pub(crate) generation: usize,
+  auto_shrink: Option<f32>,
+  last_shrink: Option<(usize, usize)>,
+  #[cfg(feature = "metrics")]
+  pub(crate) stats: OpStats,
   phantom: PhantomData<N>,
 }
 
+/// Kinds of structural operations, counted if the `metrics` feature is
+/// enabled.
+#[derive(Clone, Copy)]
+pub(crate) enum Op {
+  Push,
+  Pop,
+  Insert,
+  Remove,
+  Move,
+}
+
 impl<K, N, M> KeyNodeList<K, N, M>
 where
   M: Default,
@@ -31,6 +71,21 @@ where
   }
 }
 
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq,
+  M: Map<K, N> + Default,
+{
+  /// Creates an empty linked list with space for at least `capacity`
+  /// key-node pairs, if the underlying map supports preallocation.
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    let mut map = M::default();
+    map.reserve(capacity);
+    Self::with_map(map)
+  }
+}
+
 impl<K, N, M> KeyNodeList<K, N, M>
 where
   M: Map<K, N>,
@@ -42,10 +97,82 @@ where
       nodes: map,
       head: None,
       tail: None,
+      current: None,
+      unlinked: 0,
+      generation: 0,
+      auto_shrink: None,
+      last_shrink: None,
+      #[cfg(feature = "metrics")]
+      stats: OpStats::default(),
       phantom: PhantomData,
     }
   }
 
+  /// Records a structural operation for metrics.
+  #[inline]
+  pub(crate) fn record_op(&mut self, op: Op) {
+    self.record_ops(op, 1);
+  }
+
+  /// Records a structural operation performed on `count` pairs for metrics.
+  #[inline]
+  pub(crate) fn record_ops(&mut self, op: Op, count: usize) {
+    self.bump_generation();
+    #[cfg(feature = "metrics")]
+    self.stats.record(op, count);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (op, count);
+  }
+
+  /// Marks the order indices of the list as stale after a structural
+  /// mutation, see [`OrderIndex`](crate::OrderIndex).
+  #[inline]
+  pub(crate) fn bump_generation(&mut self) {
+    self.generation = self.generation.wrapping_add(1);
+  }
+
+  /// Returns the auto-shrink threshold set by
+  /// [`KeyNodeList::set_auto_shrink`], or `None` if auto-shrink is
+  /// disabled.
+  #[inline]
+  pub fn auto_shrink(&self) -> Option<f32> {
+    self.auto_shrink
+  }
+
+  /// Enables auto-shrink: after a key-node pair is removed, if the load
+  /// factor of the underlying map (length divided by capacity) drops below
+  /// `threshold`, [`Map::shrink_to_fit`] is called.
+  ///
+  /// Auto-shrink is disabled by default. It adds a capacity check to every
+  /// removal, and shrinking reallocates the map, which takes *O*(n) time.
+  ///
+  /// A shrunk map may still be nearly empty by the threshold's measure,
+  /// since maps round their capacity up. Shrinking on every such removal
+  /// would make removing all pairs take *O*(n²) time, so after a shrink,
+  /// the map is not shrunk again until the list length drops to half of
+  /// its length at that shrink, or the map grows. This keeps removals
+  /// amortized *O*(1).
+  ///
+  /// # Panics
+  ///
+  /// Panics if `threshold` is not in the open interval `(0, 1)`, or is NaN.
+  #[inline]
+  pub fn set_auto_shrink(&mut self, threshold: f32) {
+    assert!(
+      threshold > 0.0 && threshold < 1.0,
+      "auto-shrink threshold must be in (0, 1)"
+    );
+    self.auto_shrink = Some(threshold);
+    self.last_shrink = None;
+  }
+
+  /// Disables auto-shrink.
+  #[inline]
+  pub fn disable_auto_shrink(&mut self) {
+    self.auto_shrink = None;
+    self.last_shrink = None;
+  }
+
   /// Returns a reference to the front key, or `None` if the list is empty.
   ///
   /// This operation should compute in *O*(1) time.
@@ -64,6 +191,9 @@ where
 
   /// Returns the number of key-node pairs in the list.
   ///
+  /// This includes the pairs unlinked by [`KeyNodeList::unlink_all`], which
+  /// are not visited by the iterators, see [`KeyNodeList::linked_len`].
+  ///
   /// This operation should compute in *O*(1) time.
   #[inline]
   pub fn len(&self) -> usize {
@@ -78,35 +208,76 @@ where
     self.nodes.is_empty()
   }
 
+  /// Returns the number of key-node pairs that are linked into the list,
+  /// which is the number of pairs visited by the iterators and cursors.
+  ///
+  /// This is equal to [`KeyNodeList::len`], unless some pairs were unlinked
+  /// by [`KeyNodeList::unlink_all`] and have not been linked again.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn linked_len(&self) -> usize {
+    self.len() - self.unlinked
+  }
+
+  /// Returns the number of key-node pairs the list can hold without
+  /// reallocating the underlying map.
+  ///
+  /// For maps that do not manage their capacity, this is the same as
+  /// [`KeyNodeList::len`].
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    self.nodes.capacity()
+  }
+
   /// Removes all key-node pairs in the list.
   #[inline]
   pub fn clear(&mut self) {
     self.nodes.clear();
     self.head = None;
     self.tail = None;
+    self.current = None;
+    self.unlinked = 0;
+    self.bump_generation();
+  }
+
+  /// Removes all key-node pairs in the list, keeping the allocated memory of
+  /// the underlying map for reuse.
+  ///
+  /// This is equivalent to [`KeyNodeList::clear`], but states the guarantee
+  /// explicitly: the capacity is retained as long as [`Map::clear`] of `M`
+  /// retains it, which is the case for [`HashMap`]. Auto-shrink does not
+  /// apply to this method.
+  #[inline]
+  pub fn reset(&mut self) {
+    self.clear();
   }
 
   /// Returns an iterator over all keys and nodes.
   /// The iterator element type is `(&'a K, &'a N)`.
   #[inline]
-  pub fn iter(&self) -> Iter<K, N, M> {
+  pub fn iter(&self) -> Iter<'_, K, N, M> {
     Iter {
       list: self,
       key: self.head.as_ref(),
+      back: self.tail.as_ref(),
+      len: self.linked_len(),
     }
   }
 
   /// Returns an iterator over all keys.
   /// The iterator element type is `&'a K`.
   #[inline]
-  pub fn keys(&self) -> Keys<K, N, M> {
+  pub fn keys(&self) -> Keys<'_, K, N, M> {
     Keys { iter: self.iter() }
   }
 
   /// Returns an iterator over all nodes.
   /// The iterator element type is `&'a N`.
   #[inline]
-  pub fn nodes(&self) -> Nodes<K, N, M> {
+  pub fn nodes(&self) -> Nodes<'_, K, N, M> {
     Nodes { iter: self.iter() }
   }
 }
@@ -114,283 +285,2339 @@ where
 impl<K, N, M> KeyNodeList<K, N, M>
 where
   K: Hash + Eq,
-  M: Map<K, N>,
+  M: MapCapacity<K, N>,
+{
+  /// Tries to reserve capacity for at least `additional` more key-node
+  /// pairs to be inserted in the list.
+  ///
+  /// If the capacity overflows, or the allocator reports a failure,
+  /// then an error is returned.
+  #[inline]
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    self.nodes.try_reserve(additional)
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq,
+  M: Map<K, N>,
+{
+  /// Reserves capacity for at least `additional` more key-node pairs to be
+  /// inserted in the list. Does nothing if the underlying map does not
+  /// support preallocation.
+  #[inline]
+  pub fn reserve(&mut self, additional: usize) {
+    self.nodes.reserve(additional);
+  }
+
+  /// Shrinks the capacity of the underlying map as much as possible. Does
+  /// nothing if the underlying map does not support shrinking.
+  ///
+  /// The links of the list are not affected.
+  #[inline]
+  pub fn shrink_to_fit(&mut self) {
+    self.nodes.shrink_to_fit();
+  }
+
+  /// Returns `true` if the linked list contains a node for the specified key.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.nodes.contains_key(key)
+  }
+
+  /// Returns `true` if the specified key is the front key of the list.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn is_front<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.head.as_ref().is_some_and(|k| k.borrow() == key)
+  }
+
+  /// Returns `true` if the specified key is the back key of the list.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn is_back<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.tail.as_ref().is_some_and(|k| k.borrow() == key)
+  }
+
+  /// Returns `true` if the linked list contains nodes for all of the
+  /// specified keys, or the iterator is empty.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the number of the specified keys.
+  pub fn contains_all<Q, I>(&self, keys: I) -> bool
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq,
+    M: MapLookup<K, N, Q>,
+    I: IntoIterator<Item = Q>,
+  {
+    keys.into_iter().all(|k| self.contains_key(&k))
+  }
+
+  /// Returns `true` if the list has no keys in common with `other`.
+  ///
+  /// [`KeyNodeList::append`] requires the lists to be disjoint, and returns
+  /// an error without changing either list otherwise.
+  ///
+  /// This operation should compute in *O*(min(n, m)) time on average,
+  /// where *n* and *m* are the lengths of the two lists.
+  pub fn is_disjoint<N2, M2>(&self, other: &KeyNodeList<K, N2, M2>) -> bool
+  where
+    N: Node<Key = K>,
+    N2: Node<Key = K>,
+    M2: Map<K, N2>,
+  {
+    if self.len() <= other.len() {
+      self.keys().all(|k| !other.contains_key(k))
+    } else {
+      other.keys().all(|k| !self.contains_key(k))
+    }
+  }
+
+  /// Returns `true` if the linked list contains a node for any of the
+  /// specified keys.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the number of the specified keys.
+  pub fn contains_any<Q, I>(&self, keys: I) -> bool
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq,
+    M: MapLookup<K, N, Q>,
+    I: IntoIterator<Item = Q>,
+  {
+    keys.into_iter().any(|k| self.contains_key(&k))
+  }
+
+  /// Returns a reference to the node corresponding to the key,
+  /// or `None` if key does not exist.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn node<Q>(&self, key: &Q) -> Option<&N>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.nodes.get(key)
+  }
+
+  /// Returns a reference to the node corresponding to the key,
+  /// or a [`MissingKey`] error containing an owned copy of the key if the
+  /// key does not exist.
+  ///
+  /// This is a non-panicking version of indexing.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn try_index<Q>(&self, key: &Q) -> Result<&N, MissingKey<Q::Owned>>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq + ToOwned,
+    M: MapLookup<K, N, Q>,
+  {
+    self
+      .nodes
+      .get(key)
+      .ok_or_else(|| MissingKey(key.to_owned()))
+  }
+
+  /// Returns a mutable reference to the node corresponding to the key,
+  /// or `None` if key does not exist.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn node_mut<Q>(&mut self, key: &Q) -> Option<&mut N>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.nodes.get_mut(key)
+  }
+
+  /// Returns the stored key and a reference to the node corresponding to
+  /// the supplied key, or `None` if key does not exist.
+  ///
+  /// The stored key may differ from the supplied key, since the supplied key
+  /// may be any borrowed form of the key type.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn get_key_node<Q>(&self, key: &Q) -> Option<(&K, &N)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.nodes.get_key_value(key)
+  }
+
+  /// Returns a reference to the front node, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn front_node(&self) -> Option<&N> {
+    self.head.as_ref().and_then(|k| self.nodes.get(k))
+  }
+
+  /// Returns a mutable reference to the front node,
+  /// or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn front_node_mut(&mut self) -> Option<&mut N> {
+    self.head.as_ref().and_then(|k| self.nodes.get_mut(k))
+  }
+
+  /// Returns a reference to the back node, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn back_node(&self) -> Option<&N> {
+    self.tail.as_ref().and_then(|k| self.nodes.get(k))
+  }
+
+  /// Returns a mutable reference to the back node,
+  /// or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn back_node_mut(&mut self) -> Option<&mut N> {
+    self.tail.as_ref().and_then(|k| self.nodes.get_mut(k))
+  }
+
+  /// Provides a cursor at the specific key.
+  ///
+  /// The cursor is pointing to the null pair if the key does not exist.
+  #[inline]
+  pub fn cursor(&self, key: K) -> Cursor<'_, K, N, M> {
+    Cursor {
+      list: self,
+      key: self.contains_key(&key).then_some(key),
+    }
+  }
+
+  /// Provides a cursor with editing operations at the specific key.
+  ///
+  /// The cursor is pointing to the null pair if the key does not exist.
+  #[inline]
+  pub fn cursor_mut(&mut self, key: K) -> CursorMut<'_, K, N, M> {
+    CursorMut {
+      key: self.contains_key(&key).then_some(key).into(),
+      list: self,
+    }
+  }
+
+  /// Returns a reference to the key of the stored current position,
+  /// or `None` if the current position is the null pair.
+  ///
+  /// Unlike a [`Cursor`], the current position is stored in the list, so
+  /// it does not borrow the list. It is reset to the null pair when the
+  /// pair at the current position is removed.
+  #[inline]
+  pub fn current(&self) -> Option<&K> {
+    self.current.as_ref()
+  }
+
+  /// Sets the stored current position to the specific key.
+  ///
+  /// The current position is set to the null pair if `key` is `None` or
+  /// the key does not exist.
+  #[inline]
+  pub fn set_current(&mut self, key: Option<K>) {
+    self.current = key.filter(|k| self.contains_key(k));
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Returns an iterator over all keys with their previous and next keys.
+  /// The iterator element type is `(&'a K, Option<&'a K>, Option<&'a K>)`.
+  ///
+  /// The links are read directly from the nodes in a single pass,
+  /// in list order.
+  #[inline]
+  pub fn iter_with_links(&self) -> impl Iterator<Item = (&K, Option<&K>, Option<&K>)> {
+    self.iter().map(|(k, n)| (k, n.prev(), n.next()))
+  }
+
+  /// Returns an iterator over all adjacent node pairs in list order.
+  /// The iterator element type is `(&'a N, &'a N)`, where the first node
+  /// is the current node and the second node is its next node.
+  ///
+  /// The list is walked only once, the iterator yields `len() - 1` pairs
+  /// for a non-empty list.
+  pub fn node_pairs(&self) -> impl Iterator<Item = (&N, &N)> {
+    let mut cur = self.front_node();
+    iter::from_fn(move || {
+      let n = cur?;
+      let next = self.nodes.get(n.next()?)?;
+      cur = Some(next);
+      Some((n, next))
+    })
+  }
+
+  /// Returns an iterator over the keys that are in the list but not in
+  /// `other`, in list order.
+  /// The iterator element type is `&'a K`.
+  #[inline]
+  pub fn keys_not_in<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a K> {
+    self.keys().filter(move |k| !other.contains_key(*k))
+  }
+
+  /// Returns an iterator over the keys that are in both the list and
+  /// `other`, in list order.
+  /// The iterator element type is `&'a K`.
+  #[inline]
+  pub fn keys_in_both<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a K> {
+    self.keys().filter(move |k| other.contains_key(*k))
+  }
+
+  /// Returns an iterator over every `step`-th key starting from the front
+  /// key, a `step` of 1 yields all keys.
+  /// The iterator element type is `&'a K`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `step` is 0.
+  #[inline]
+  pub fn step_by_keys(&self, step: usize) -> impl Iterator<Item = &K> {
+    assert!(step != 0, "`step` of `step_by_keys` must not be 0");
+    self.keys().step_by(step)
+  }
+
+  /// Walks the list and `other` in list order, and returns the index of the
+  /// first position where the keys or the nodes differ, or where one of the
+  /// lists ends. Returns `None` if the two lists are identical in order.
+  ///
+  /// Nodes are compared by [`PartialEq`] of `N`, which usually includes the
+  /// link fields. So a difference of keys may also be reported at the
+  /// previous position, where the next keys of the nodes differ.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn first_order_difference(&self, other: &Self) -> Option<usize>
+  where
+    N: PartialEq,
+  {
+    let mut iter1 = self.iter();
+    let mut iter2 = other.iter();
+    let mut index = 0;
+    loop {
+      match (iter1.next(), iter2.next()) {
+        (None, None) => return None,
+        (Some(p1), Some(p2)) if p1 == p2 => index += 1,
+        _ => return Some(index),
+      }
+    }
+  }
+
+  /// Folds every key-node pair into an accumulator by applying `f`,
+  /// from the front of the list to the back.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn fold<B, F>(&self, init: B, mut f: F) -> B
+  where
+    F: FnMut(B, &K, &N) -> B,
+  {
+    self.iter().fold(init, |acc, (k, n)| f(acc, k, n))
+  }
+
+  /// Folds every key-node pair into an accumulator by applying `f`,
+  /// from the back of the list to the front.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn rfold<B, F>(&self, init: B, mut f: F) -> B
+  where
+    F: FnMut(B, &K, &N) -> B,
+  {
+    let mut acc = init;
+    let mut pair = self.tail.as_ref().and_then(|k| self.get_key_node(k));
+    while let Some((k, n)) = pair {
+      acc = f(acc, k, n);
+      pair = n.prev().and_then(|k| self.get_key_node::<K>(k));
+    }
+    acc
+  }
+
+  /// Returns an iterator over the key-node pairs from `from` to `to`
+  /// (inclusive) in reverse order, by following the previous keys.
+  /// The iterator element type is `(&'a K, &'a N)`.
+  ///
+  /// If `to` can not be reached from `from`, the iterator stops at the
+  /// front of the list. If `from` does not exist, the iterator is empty.
+  pub fn range_rev<'a, Q>(
+    &'a self,
+    from: &Q,
+    to: &'a Q,
+  ) -> impl Iterator<Item = (&'a K, &'a N)> + 'a
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let mut cur = self.get_key_node(from);
+    let mut done = false;
+    iter::from_fn(move || {
+      if done {
+        return None;
+      }
+      let (k, n) = cur?;
+      done = k.borrow() == to;
+      cur = n.prev().and_then(|k| self.get_key_node::<K>(k));
+      Some((k, n))
+    })
+  }
+
+  /// Returns an iterator over the key-node pairs starting at the specified
+  /// key and proceeding to the back of the list.
+  /// The iterator element type is `(&'a K, &'a N)`.
+  ///
+  /// The iterator is empty if key does not exist.
+  #[inline]
+  pub fn iter_from<Q>(&self, key: &Q) -> IterFrom<'_, K, N, M>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let key = self.get_key_node(key).map(|(k, _)| k);
+    IterFrom {
+      iter: Iter {
+        list: self,
+        key,
+        back: key.and(self.tail.as_ref()),
+        // the remaining length is unknown, see `IterFrom::size_hint`
+        len: 0,
+      },
+    }
+  }
+
+  /// Returns an iterator over the key-node pairs starting at the specified
+  /// key and proceeding to the front of the list.
+  /// The iterator element type is `(&'a K, &'a N)`.
+  ///
+  /// The iterator is empty if key does not exist.
+  #[inline]
+  pub fn riter_from<Q>(&self, key: &Q) -> Rev<IterFrom<'_, K, N, M>>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let key = self.get_key_node(key).map(|(k, _)| k);
+    IterFrom {
+      iter: Iter {
+        list: self,
+        key: key.and(self.head.as_ref()),
+        back: key,
+        // the remaining length is unknown, see `IterFrom::size_hint`
+        len: 0,
+      },
+    }
+    .rev()
+  }
+
+  /// Returns the index of the specified key in list order,
+  /// or `None` if key does not exist.
+  ///
+  /// This operation should compute in *O*(n) time, since the list must be
+  /// walked from the front.
+  pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    if self.contains_key(key) {
+      self.keys().position(|k| k.borrow() == key)
+    } else {
+      None
+    }
+  }
+
+  /// Returns the index of the specified key in list order together with
+  /// the node, or `None` if key does not exist.
+  ///
+  /// This operation should compute in *O*(n) time, since the list must be
+  /// walked from the front to find the index. The walk is done only once.
+  pub fn find<Q>(&self, key: &Q) -> Option<(usize, &N)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    if self.contains_key(key) {
+      self
+        .iter()
+        .enumerate()
+        .find(|(_, (k, _))| (*k).borrow() == key)
+        .map(|(i, (_, n))| (i, n))
+    } else {
+      None
+    }
+  }
+
+  /// Returns the key-node pair at the specified index in list order,
+  /// or `None` if `index` is out of bounds.
+  ///
+  /// This operation should compute in *O*(index) time, since the list must
+  /// be walked from the front.
+  #[inline]
+  pub fn get_index(&self, index: usize) -> Option<(&K, &N)> {
+    self.iter().nth(index)
+  }
+
+  /// Unlinks all key-node pairs, leaving the list empty for traversal, but
+  /// keeping all pairs in the underlying map.
+  ///
+  /// All nodes have no previous and next keys afterwards, so the nodes can
+  /// still be accessed by their keys while a new order is being built. The
+  /// unlinked pairs are still counted by [`KeyNodeList::len`], but not by
+  /// [`KeyNodeList::linked_len`]. They can be removed, or linked again by
+  /// [`KeyNodeList::reorder`] or by moving them, e.g. with
+  /// [`KeyNodeList::move_to_back`]. Other operations that take an unlinked
+  /// key as the position to work at, such as cursors, are not supported and
+  /// may leave the list in an unspecified state.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn unlink_all(&mut self) {
+    let mut key = self.head.take();
+    while let Some(k) = key {
+      let node = self.nodes.get_mut(&k).unwrap();
+      *node_prev_mut!(node) = None;
+      key = node_next_mut!(node).take();
+    }
+    self.tail = None;
+    self.current = None;
+    self.unlinked = self.len();
+    self.bump_generation();
+  }
+
+  /// Returns `true` if a cycle can be found by following the next keys from
+  /// the front key of the list.
+  ///
+  /// The list created by the provided operations never contains cycles.
+  /// However, a broken implementation of [`Node`] may introduce cycles,
+  /// which make the iteration of the list never stop.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn has_cycle(&self) -> bool {
+    let next = |k: &K| self.node(k).and_then(|n| n.next());
+    // Floyd's cycle detection algorithm
+    let mut slow = self.head.as_ref();
+    let mut fast = self.head.as_ref();
+    while let Some(k) = fast.and_then(next).and_then(next) {
+      fast = Some(k);
+      slow = slow.and_then(next);
+      if slow == fast {
+        return true;
+      }
+    }
+    false
+  }
+
+  /// Returns a wrapper that formats only the keys of the list, in order,
+  /// like `[1, 2, 3]`.
+  ///
+  /// This is useful for tracing the key order of a large list without
+  /// printing the nodes.
+  #[inline]
+  pub fn debug_keys(&self) -> impl fmt::Debug + '_
+  where
+    K: fmt::Debug,
+  {
+    DebugKeys { list: self }
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: IndexKey,
+  N: Node<Key = K>,
+  M: MapIter<K, N>,
+{
+  /// Returns an iterator over all keys and nodes in list order,
+  /// with mutable references to the nodes.
+  /// The iterator element type is `(&'a K, &'a mut N)`.
+  ///
+  /// The underlying map can not provide mutable references to multiple
+  /// nodes by keys at once, so the list order is collected before the
+  /// iteration. Modifying the links of the nodes through the references,
+  /// for example the public fields of a custom [`Node`], is a logic error.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  #[inline]
+  pub fn iter_mut(&mut self) -> IterMut<'_, K, N> {
+    IterMut {
+      pairs: self.pairs_mut().into_iter(),
+    }
+  }
+
+  /// Returns an iterator over all nodes in list order, with mutable
+  /// references to the nodes.
+  /// The iterator element type is `&'a mut N`.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  #[inline]
+  pub fn nodes_mut(&mut self) -> NodesMut<'_, K, N> {
+    NodesMut {
+      iter: self.iter_mut(),
+    }
+  }
+
+  /// Returns an iterator over all keys and nodes in reverse order,
+  /// with mutable references to the nodes.
+  /// The iterator element type is `(&'a K, &'a mut N)`.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  #[inline]
+  pub fn iter_mut_rev(&mut self) -> impl Iterator<Item = (&K, &mut N)> {
+    self.iter_mut().rev()
+  }
+
+  /// Returns the keys that are present in the list but can not be reached
+  /// by following the next keys from the front key of the list.
+  ///
+  /// The list created by the provided operations never contains
+  /// unreachable keys. However, a broken implementation of [`Node`] may
+  /// leave some pairs out of the links. The returned keys are in
+  /// arbitrary order.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn unreachable_keys(&self) -> Vec<&K> {
+    // walking stops after `len` steps in case the links contain a cycle
+    let reachable: KeyIndex<&K, ()> = self.keys().take(self.len()).map(|k| (k, ())).collect();
+    self
+      .nodes
+      .iter()
+      .filter(|(k, _)| !reachable.contains_key(k))
+      .map(|(k, _)| k)
+      .collect()
+  }
+
+  /// Returns all keys and mutable references to nodes in list order.
+  fn pairs_mut(&mut self) -> Vec<(&K, &mut N)> {
+    // the map can not provide mutable references to multiple nodes by keys
+    // at once, so we take all pairs from the map, and then follow the links
+    // through an index of the pairs, reading the next key of each node
+    // before moving its reference out
+    let mut pairs: Vec<_> = self.nodes.iter_mut().map(Some).collect();
+    let index: KeyIndex<&K, usize> = pairs
+      .iter()
+      .enumerate()
+      .filter_map(|(i, p)| p.as_ref().map(|(k, _)| (*k, i)))
+      .collect();
+    let position = |key: Option<&K>| key.and_then(|k| index.get(k).copied());
+    let mut ordered = Vec::with_capacity(pairs.len());
+    let mut cur = position(self.head.as_ref());
+    while let Some((k, n)) = cur.and_then(|i| pairs[i].take()) {
+      cur = position(n.next());
+      ordered.push((k, n));
+    }
+    ordered
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  M: Map<K, N>,
+{
+  /// Provides a cursor at the front key-node pair.
+  ///
+  /// The cursor is pointing to the null pair if the list is empty.
+  #[inline]
+  pub fn cursor_front(&self) -> Cursor<'_, K, N, M> {
+    Cursor {
+      list: self,
+      key: self.head.clone(),
+    }
+  }
+
+  /// Provides a cursor with editing operations at the front key-node pair.
+  ///
+  /// The cursor is pointing to the null pair if the list is empty.
+  #[inline]
+  pub fn cursor_front_mut(&mut self) -> CursorMut<'_, K, N, M> {
+    CursorMut {
+      key: self.head.clone().into(),
+      list: self,
+    }
+  }
+
+  /// Provides a cursor at the back key-node pair.
+  ///
+  /// The cursor is pointing to the null pair if the list is empty.
+  #[inline]
+  pub fn cursor_back(&self) -> Cursor<'_, K, N, M> {
+    Cursor {
+      list: self,
+      key: self.tail.clone(),
+    }
+  }
+
+  /// Provides a cursor with editing operations at the back key-node pair.
+  ///
+  /// The cursor is pointing to the null pair if the list is empty.
+  #[inline]
+  pub fn cursor_back_mut(&mut self) -> CursorMut<'_, K, N, M> {
+    CursorMut {
+      key: self.tail.clone().into(),
+      list: self,
+    }
+  }
+
+  /// Provides a cursor at the specific key, looking the key up by any of
+  /// its borrowed forms, for example `&str` for `String` keys.
+  ///
+  /// The cursor is pointing to the null pair if the key does not exist.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn cursor_at<Q>(&self, key: &Q) -> Cursor<'_, K, N, M>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    Cursor {
+      list: self,
+      key: self.get_key_node(key).map(|(k, _)| k.clone()),
+    }
+  }
+
+  /// Provides a cursor with editing operations at the specific key,
+  /// looking the key up by any of its borrowed forms.
+  ///
+  /// The cursor is pointing to the null pair if the key does not exist.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn cursor_at_mut<Q>(&mut self, key: &Q) -> CursorMut<'_, K, N, M>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    CursorMut {
+      key: self.get_key_node(key).map(|(k, _)| k.clone()).into(),
+      list: self,
+    }
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Creates a consuming iterator over all keys.
+  /// The list cannot be used after calling this.
+  /// The iterator element type is `K`.
+  #[inline]
+  pub fn into_keys(self) -> IntoKeys<K, N, M> {
+    IntoKeys {
+      iter: self.into_iter(),
+    }
+  }
+
+  /// Creates a consuming iterator over all nodes.
+  /// The list cannot be used after calling this.
+  /// The iterator element type is `N`.
+  #[inline]
+  pub fn into_nodes(self) -> IntoNodes<K, N, M> {
+    IntoNodes {
+      iter: self.into_iter(),
+    }
+  }
+
+  /// Creates a list from the given key-node pairs in order. The links of the
+  /// nodes are overwritten to match the order.
+  ///
+  /// Returns a [`DuplicateKey`] error containing the first key that appears
+  /// more than once.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn from_entries_in_order<I>(entries: I) -> Result<Self, DuplicateKey<K>>
+  where
+    I: IntoIterator<Item = (K, N)>,
+    M: Default,
+  {
+    let mut list = Self::new();
+    for (key, node) in entries {
+      list
+        .push_back(key, node)
+        .map_err(|(key, _)| DuplicateKey(key))?;
+    }
+    Ok(list)
+  }
+
+  /// Consumes the list, and creates a new list by applying `f` to all
+  /// key-node pairs in list order. `f` returns the new key and a value that
+  /// can be converted into the new node.
+  ///
+  /// The links of the new nodes are rebuilt from the new keys. If a new key
+  /// collides with a key produced earlier, the pair is dropped, and the
+  /// earlier pair is kept.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn map<K2, T, N2, M2, F>(self, mut f: F) -> KeyNodeList<K2, N2, M2>
+  where
+    K2: Hash + Eq + Clone,
+    T: Into<N2>,
+    N2: Node<Key = K2>,
+    M2: Map<K2, N2> + Default,
+    F: FnMut(K, N) -> (K2, T),
+  {
+    let mut list = KeyNodeList::new();
+    for (key, node) in self {
+      let (key, node) = f(key, node);
+      let _ = list.push_back(key, node);
+    }
+    list
+  }
+
+  /// Consumes the list, passes all key-node pairs to `f` in list order,
+  /// and returns the empty backing map.
+  ///
+  /// The returned map retains its allocated memory, so it can be reused
+  /// to create a new list by [`KeyNodeList::with_map`].
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn recycle_into<F>(mut self, mut f: F) -> M
+  where
+    F: FnMut(K, N),
+  {
+    // keep the allocated memory
+    self.auto_shrink = None;
+    while let Some((key, node)) = self.pop_front() {
+      f(key, node);
+    }
+    // drop the unreachable nodes, if any
+    self.nodes.clear();
+    self.nodes
+  }
+
+  /// Consumes the list, returns the front key-node pair and the rest of
+  /// the list, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn split_first(mut self) -> Option<((K, N), Self)> {
+    self.pop_front().map(|pair| (pair, self))
+  }
+
+  /// Consumes the list, returns the back key-node pair and the rest of
+  /// the list, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn split_last(mut self) -> Option<((K, N), Self)> {
+    self.pop_back().map(|pair| (pair, self))
+  }
+
+  /// Consumes the list, splits it into two lists of sizes `ceil(len / 2)`
+  /// and `floor(len / 2)`, preserving the order.
+  ///
+  /// On an empty or single-element list, the second list is empty.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn split_half(mut self) -> (Self, Self)
+  where
+    M: Default,
+  {
+    match self.keys().nth(self.len().div_ceil(2)) {
+      Some(k) => {
+        let mid = k.clone();
+        let second = self.split_from(mid);
+        (self, second)
+      }
+      None => (self, Self::new()),
+    }
+  }
+
+  /// Splits the list into two at the specified key. Returns everything
+  /// after the key as a new list, and keeps everything up to and including
+  /// the key in the list.
+  ///
+  /// Returns `None` if key does not exist. Returns an empty list if key is
+  /// the back key of the list.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the length of the returned list.
+  pub fn split_off<Q>(&mut self, key: &Q) -> Option<Self>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q> + Default,
+  {
+    let next = self.node(key)?.next().cloned();
+    Some(match next {
+      Some(next) => self.split_from(next),
+      None => Self::new(),
+    })
+  }
+
+  /// Moves the key-node pairs from `first` to the back of the list to a new
+  /// list, `first` must exist in the list.
+  fn split_from(&mut self, first: K) -> Self
+  where
+    M: Default,
+  {
+    let mut second = Self::new();
+    let mut cur = Some(first.clone());
+    let mut count = 0;
+    while let Some((k, n)) = cur.and_then(|k| self.nodes.remove_entry(&k)) {
+      cur = n.next().cloned();
+      count += 1;
+      if self.current.as_ref() == Some(&k) {
+        second.current = self.current.take();
+      }
+      let result = second.nodes.insert(k, n);
+      debug_assert!(result.is_ok(), "key already exists in the list");
+    }
+    // relink the boundary
+    let prev = node_prev_mut!(second, &first).take();
+    if let Some(k) = &prev {
+      *node_next_mut!(self, k) = None;
+    }
+    second.head = Some(first);
+    second.tail = mem::replace(&mut self.tail, prev);
+    if self.tail.is_none() {
+      self.head = None;
+    }
+    self.record_ops(Op::Pop, count);
+    second.record_ops(Op::Push, count);
+    second
+  }
+
+  /// Returns a mutable reference to the node at the specified index in
+  /// list order, or `None` if `index` is out of bounds.
+  ///
+  /// This is the mutable counterpart of [`KeyNodeList::get_index`].
+  ///
+  /// This operation should compute in *O*(index) time, since the list must
+  /// be walked from the front.
+  pub fn node_at_index_mut(&mut self, index: usize) -> Option<&mut N> {
+    let key = self.keys().nth(index)?.clone();
+    self.nodes.get_mut(&key)
+  }
+
+  /// Returns a mutable reference to the front node and a clone of the
+  /// second key, or `None` if the list is empty.
+  ///
+  /// The second key is `None` if the list contains only one pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn front_mut_with_next_key(&mut self) -> Option<(&mut N, Option<K>)> {
+    self.front_node_mut().map(|n| {
+      let next = n.next().cloned();
+      (n, next)
+    })
+  }
+
+  /// Returns a mutable reference to the back node and a clone of the
+  /// second-to-last key, or `None` if the list is empty.
+  ///
+  /// The second-to-last key is `None` if the list contains only one pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn back_mut_with_prev_key(&mut self) -> Option<(&mut N, Option<K>)> {
+    self.back_node_mut().map(|n| {
+      let prev = n.prev().cloned();
+      (n, prev)
+    })
+  }
+
+  /// Moves the stored current position to the next key-node pair.
+  ///
+  /// If the current position is the null pair then this will move it to
+  /// the first key-node pair. If it is the last key-node pair then this
+  /// will move it to the null pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn advance_current(&mut self) {
+    self.current = match &self.current {
+      Some(k) => self.node(k).and_then(|n| n.next().cloned()),
+      None => self.head.clone(),
+    };
+  }
+
+  /// Moves the stored current position to the previous key-node pair.
+  ///
+  /// If the current position is the null pair then this will move it to
+  /// the last key-node pair. If it is the first key-node pair then this
+  /// will move it to the null pair.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn retreat_current(&mut self) {
+    self.current = match &self.current {
+      Some(k) => self.node(k).and_then(|n| n.prev().cloned()),
+      None => self.tail.clone(),
+    };
+  }
+
+  /// Replaces the front node with a new node, keeping the front key and
+  /// its position, and returns the old node.
+  ///
+  /// The previous and next pointers of the old node are copied to the new
+  /// node. Returns `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn replace_front<T: Into<N>>(&mut self, node: T) -> Option<N> {
+    self.cursor_front_mut().replace_current(node)
+  }
+
+  /// Replaces the back node with a new node, keeping the back key and
+  /// its position, and returns the old node.
+  ///
+  /// The previous and next pointers of the old node are copied to the new
+  /// node. Returns `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn replace_back<T: Into<N>>(&mut self, node: T) -> Option<N> {
+    self.cursor_back_mut().replace_current(node)
+  }
+
+  /// Adds a key-node pair first in the list.
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn push_front<T: Into<N>>(&mut self, key: K, node: T) -> Result<(), (K, T)> {
+    self.nodes.insert(key.clone(), node).map(|_| {
+      let next = self.head.replace(key.clone());
+      match &next {
+        Some(k) => *node_prev_mut!(self, k) = Some(key.clone()),
+        None => self.tail = Some(key.clone()),
+      }
+      let node = self.node_mut(&key).unwrap();
+      *node_prev_mut!(node) = None;
+      *node_next_mut!(node) = next;
+      self.record_op(Op::Push);
+    })
+  }
+
+  /// Adds a key-node pair back in the list.
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn push_back<T: Into<N>>(&mut self, key: K, node: T) -> Result<(), (K, T)> {
+    self.nodes.insert(key.clone(), node).map(|_| {
+      let prev = self.tail.replace(key.clone());
+      match &prev {
+        Some(k) => *node_next_mut!(self, k) = Some(key.clone()),
+        None => self.head = Some(key.clone()),
+      }
+      let node = self.node_mut(&key).unwrap();
+      *node_prev_mut!(node) = prev;
+      *node_next_mut!(node) = None;
+      self.record_op(Op::Push);
+    })
+  }
+
+  /// Adds a key-node pair to the front of the list, `key` must not exist
+  /// in the list.
+  #[inline]
+  pub(crate) fn push_front_unchecked<T: Into<N>>(&mut self, key: K, node: T) {
+    let result = self.push_front(key, node);
+    debug_assert!(result.is_ok(), "key already exists in the list");
+  }
+
+  /// Adds a key-node pair to the back of the list, `key` must not exist
+  /// in the list.
+  #[inline]
+  pub(crate) fn push_back_unchecked<T: Into<N>>(&mut self, key: K, node: T) {
+    let result = self.push_back(key, node);
+    debug_assert!(result.is_ok(), "key already exists in the list");
+  }
+
+  /// Adds a key-node pair back in the list, the node is built by calling
+  /// `build` with a reference to the key. Returns a mutable reference to
+  /// the inserted node.
+  ///
+  /// `build` is called after the key is checked, but before the key is
+  /// moved into the list, so the reference does not point to the stored
+  /// key. The node can be derived from the key, but can not borrow from it.
+  ///
+  /// If `key` already exists, `build` is not called, and returns an error
+  /// containing `key`.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn push_back_with_key<F>(&mut self, key: K, build: F) -> Result<&mut N, K>
+  where
+    F: FnOnce(&K) -> N,
+  {
+    if self.contains_key(&key) {
+      return Err(key);
+    }
+    let node = build(&key);
+    self.push_back_unchecked(key.clone(), node);
+    Ok(self.node_mut(&key).unwrap())
+  }
+
+  /// Returns a mutable reference to the node of the given key. If the key
+  /// does not exist, the key and `default` are pushed to the back of the
+  /// list first.
+  ///
+  /// The position of an existing key is unchanged, and `default` is dropped
+  /// in this case.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn get_or_insert_back<T: Into<N>>(&mut self, key: K, default: T) -> &mut N {
+    self.entry(key).or_insert_back(default)
+  }
+
+  /// Returns a mutable reference to the node of the given key. If the key
+  /// does not exist, the key and `default` are pushed to the front of the
+  /// list first.
+  ///
+  /// The position of an existing key is unchanged, and `default` is dropped
+  /// in this case.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn get_or_insert_front<T: Into<N>>(&mut self, key: K, default: T) -> &mut N {
+    self.entry(key).or_insert_front(default)
+  }
+
+  /// Adds a key first in the list.
+  ///
+  /// If `key` already exists, returns an error containing `key`.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn push_key_front(&mut self, key: K) -> Result<(), K>
+  where
+    (): Into<N>,
+  {
+    self.push_front(key, ()).map_err(|(k, _)| k)
+  }
+
+  /// Adds a key back in the list.
+  ///
+  /// If `key` already exists, returns an error containing `key`.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn push_key_back(&mut self, key: K) -> Result<(), K>
+  where
+    (): Into<N>,
+  {
+    self.push_back(key, ()).map_err(|(k, _)| k)
+  }
+
+  /// Removes the first key-node pair and returns it, or `None` if the list
+  /// is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn pop_front(&mut self) -> Option<(K, N)> {
+    let key = self.head.clone()?;
+    let pair = self.unlink_remove(&key)?;
+    self.record_op(Op::Pop);
+    Some(pair)
+  }
+
+  /// Removes the last key-node pair and returns it, or `None` if the list
+  /// is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn pop_back(&mut self) -> Option<(K, N)> {
+    let key = self.tail.clone()?;
+    let pair = self.unlink_remove(&key)?;
+    self.record_op(Op::Pop);
+    Some(pair)
+  }
+
+  /// Inserts a key-node pair into the underlying hash map without changing
+  /// the previous and next keys of the node, or the front and back keys of
+  /// the list.
+  ///
+  /// This is an escape hatch for bulk importing nodes whose links are set
+  /// by the caller. The list is in an inconsistent state after calling
+  /// this method, [`KeyNodeList::rebuild_from_links`] must be called and
+  /// succeed before the list is used again. To push a pair safely, use
+  /// [`KeyNodeList::push_back`], which overwrites the links of the node.
+  ///
+  /// If `key` already exists, returns an error containing `key` and `node`.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn push_back_raw(&mut self, key: K, node: N) -> Result<(), (K, N)> {
+    self.bump_generation();
+    self.nodes.insert(key, node)
+  }
+
+  /// Takes the underlying map out of the list, leaving an empty map in
+  /// its place, and returns the taken map.
+  ///
+  /// The nodes in the returned map retain their links, so the map can be
+  /// installed back by [`KeyNodeList::restore_map`].
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn take_map(&mut self) -> M
+  where
+    M: Default,
+  {
+    self.head = None;
+    self.tail = None;
+    self.current = None;
+    self.unlinked = 0;
+    self.bump_generation();
+    mem::take(&mut self.nodes)
+  }
+
+  /// Removes up to `n` key-node pairs from the front of the list, and
+  /// returns them in list order.
+  ///
+  /// Stops early if the list becomes empty.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn pop_front_n(&mut self, n: usize) -> Vec<(K, N)> {
+    iter::from_fn(|| self.pop_front()).take(n).collect()
+  }
+
+  /// Removes up to `n` key-node pairs from the back of the list, and
+  /// returns them in the order they were popped, i.e. from the back of
+  /// the list to the front.
+  ///
+  /// Stops early if the list becomes empty.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn pop_back_n(&mut self, n: usize) -> Vec<(K, N)> {
+    iter::from_fn(|| self.pop_back()).take(n).collect()
+  }
+
+  /// Removes the key-node pair at the given key and returns it,
+  /// or returns `None` if `key` does not exists.
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, N)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let pair = self.unlink_remove(key)?;
+    self.record_op(Op::Remove);
+    Some(pair)
+  }
+
+  /// Removes the key-node pair with the specified key, and returns the
+  /// index it occupied in list order together with the pair.
+  ///
+  /// If `key` does not exist, returns `None`.
+  ///
+  /// Unlike [`KeyNodeList::remove`], this operation should compute in
+  /// *O*(n) time, since the list must be walked from the front to find
+  /// the index.
+  pub fn remove_indexed<Q>(&mut self, key: &Q) -> Option<(usize, K, N)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let index = self.get_index_of(key)?;
+    self.remove(key).map(|(k, n)| (index, k, n))
+  }
+
+  /// Moves the key-node pair at `key` to the position just before the
+  /// pair at `target`.
+  ///
+  /// Returns `false` if any of the keys does not exist, or the two keys
+  /// are equal, in which case the list is unchanged.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn move_before<Q>(&mut self, key: &Q, target: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    match self.move_keys(key, target) {
+      Some((key, target)) => {
+        self.unlink(&key);
+        let prev = self.nodes.get(&target).and_then(|n| n.prev().cloned());
+        self.link_between(&key, prev, Some(target));
+        self.record_op(Op::Move);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Moves the key-node pair at `key` to the position just after the
+  /// pair at `target`.
+  ///
+  /// Returns `false` if any of the keys does not exist, or the two keys
+  /// are equal, in which case the list is unchanged.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn move_after<Q>(&mut self, key: &Q, target: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    match self.move_keys(key, target) {
+      Some((key, target)) => {
+        self.unlink(&key);
+        let next = self.nodes.get(&target).and_then(|n| n.next().cloned());
+        self.link_between(&key, Some(target), next);
+        self.record_op(Op::Move);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Swaps the key-node pair at `key` with its next pair.
+  ///
+  /// Returns `false` if key does not exist or is the back key of the list,
+  /// in which case the list is unchanged.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn swap_adjacent<Q>(&mut self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let (key, prev, next) = match self.get_key_node(key) {
+      Some((k, n)) => match n.next() {
+        Some(next) => (k.clone(), n.prev().cloned(), next.clone()),
+        None => return false,
+      },
+      None => return false,
+    };
+    self.unlink(&next);
+    self.link_between(&next, prev, Some(key));
+    self.record_op(Op::Move);
+    true
+  }
+
+  /// Moves the key-node pair at `key` to the front of the list, without
+  /// removing it from the hash map.
+  ///
+  /// Returns `false` if key does not exist. If the pair is already at the
+  /// front, the list is unchanged and `true` is returned.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn move_to_front<Q>(&mut self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let key = match self.get_key_node(key) {
+      Some((k, _)) if self.head.as_ref() == Some(k) => return true,
+      Some((k, _)) => k.clone(),
+      None => return false,
+    };
+    self.unlink(&key);
+    let next = self.head.clone();
+    self.link_between(&key, None, next);
+    self.record_op(Op::Move);
+    true
+  }
+
+  /// Moves the key-node pair at `key` to the back of the list, without
+  /// removing it from the hash map.
+  ///
+  /// Returns `false` if key does not exist. If the pair is already at the
+  /// back, the list is unchanged and `true` is returned.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn move_to_back<Q>(&mut self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    let key = match self.get_key_node(key) {
+      Some((k, _)) if self.tail.as_ref() == Some(k) => return true,
+      Some((k, _)) => k.clone(),
+      None => return false,
+    };
+    self.unlink(&key);
+    let prev = self.tail.clone();
+    self.link_between(&key, prev, None);
+    self.record_op(Op::Move);
+    true
+  }
+
+  /// Returns clones of the stored keys of `key` and `target`, or `None` if
+  /// any of the keys does not exist or the two keys are equal.
+  fn move_keys<Q>(&self, key: &Q, target: &Q) -> Option<(K, K)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    if key == target {
+      return None;
+    }
+    let (key, _) = self.get_key_node(key)?;
+    let (target, _) = self.get_key_node(target)?;
+    Some((key.clone(), target.clone()))
+  }
+
+  /// Detaches the node at `key` from its neighbors, the node is kept in the
+  /// hash map with no previous and next keys.
+  pub(crate) fn unlink(&mut self, key: &K) {
+    let node = self.nodes.get_mut(key).unwrap();
+    if self.unlinked > 0 && self.head.as_ref() != Some(key) && node.prev().is_none() {
+      // the node was unlinked by `unlink_all`
+      self.unlinked -= 1;
+      return;
+    }
+    let prev = node_prev_mut!(node).take();
+    let next = node_next_mut!(node).take();
+    match &prev {
+      Some(k) => *node_next_mut!(self, k) = next.clone(),
+      None => self.head = next.clone(),
+    }
+    match &next {
+      Some(k) => *node_prev_mut!(self, k) = prev,
+      None => self.tail = prev,
+    }
+  }
+
+  /// Links the detached node at `key` between `prev` and `next`, which must
+  /// be adjacent, `None` stands for the front or back of the list.
+  pub(crate) fn link_between(&mut self, key: &K, prev: Option<K>, next: Option<K>) {
+    match &prev {
+      Some(k) => *node_next_mut!(self, k) = Some(key.clone()),
+      None => self.head = Some(key.clone()),
+    }
+    match &next {
+      Some(k) => *node_prev_mut!(self, k) = Some(key.clone()),
+      None => self.tail = Some(key.clone()),
+    }
+    let node = self.nodes.get_mut(key).unwrap();
+    *node_prev_mut!(node) = prev;
+    *node_next_mut!(node) = next;
+  }
+
+  /// Removes the key-node pair at the given key from the hash map,
+  /// and links its previous and next pairs.
+  fn unlink_remove<Q>(&mut self, key: &Q) -> Option<(K, N)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.nodes.remove_entry(key).map(|(k, n)| {
+      if self.unlinked > 0 && self.head.as_ref() != Some(&k) && n.prev().is_none() {
+        // the node was unlinked by `unlink_all`
+        self.unlinked -= 1;
+        self.shrink_if_needed();
+        return (k, n);
+      }
+      match n.prev() {
+        Some(k) => *node_next_mut!(self, k) = n.next().cloned(),
+        None => self.head = n.next().cloned(),
+      }
+      match n.next() {
+        Some(k) => *node_prev_mut!(self, k) = n.prev().cloned(),
+        None => self.tail = n.prev().cloned(),
+      }
+      if self.current.as_ref() == Some(&k) {
+        self.current = None;
+      }
+      self.shrink_if_needed();
+      (k, n)
+    })
+  }
+
+  /// Shrinks the underlying map if auto-shrink is enabled and the load
+  /// factor drops below the threshold.
+  ///
+  /// After a shrink, the map is not shrunk again until the length halves
+  /// or the map grows beyond the capacity it was shrunk to.
+  fn shrink_if_needed(&mut self) {
+    if let Some(threshold) = self.auto_shrink {
+      let len = self.len();
+      let capacity = self.nodes.capacity();
+      let settled = match self.last_shrink {
+        Some((last_len, last_capacity)) => capacity <= last_capacity && len > last_len / 2,
+        None => false,
+      };
+      if !settled && capacity > 0 && (len as f32) < threshold * capacity as f32 {
+        self.nodes.shrink_to_fit();
+        self.last_shrink = Some((len, self.nodes.capacity()));
+      }
+    }
+  }
+
+  /// Creates a new list containing clones of the key-node pairs from `from`
+  /// to `to` (inclusive) in list order. The links of the cloned nodes are
+  /// rebuilt, so `from` and `to` become the front and back of the new list.
+  ///
+  /// Returns `None` if `from` does not exist, or `to` can not be reached
+  /// from `from` by following the next keys.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the length of the range.
+  pub fn clone_range<Q>(&self, from: &Q, to: &Q) -> Option<Self>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+    N: Clone,
+    M: Default,
+  {
+    let mut range = Self::new();
+    let mut pair = self.get_key_node(from);
+    while let Some((k, n)) = pair {
+      range.push_back_unchecked(k.clone(), n.clone());
+      if k.borrow() == to {
+        return Some(range);
+      }
+      pair = n.next().and_then(|k| self.get_key_node::<K>(k));
+    }
+    None
+  }
+
+  /// Pushes all key-node pairs of the iterator to the back of the list,
+  /// converting the items into nodes by [`TryInto`].
+  ///
+  /// Stops at the first item that fails to convert or whose key already
+  /// exists, and returns an [`ExtendError`] describing the failure.
+  /// The pairs before the failed item are kept in the list.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the number of items.
+  pub fn extend_try<I, T>(&mut self, iter: I) -> Result<(), ExtendError<K, N, T::Error>>
+  where
+    I: IntoIterator<Item = (K, T)>,
+    T: TryInto<N>,
+  {
+    for (index, (key, item)) in iter.into_iter().enumerate() {
+      let node = match item.try_into() {
+        Ok(node) => node,
+        Err(error) => return Err(ExtendError::Convert { index, key, error }),
+      };
+      self
+        .push_back(key, node)
+        .map_err(|(key, node)| ExtendError::DuplicateKey { index, key, node })?;
+    }
+    Ok(())
+  }
+
+  /// Inserts all key-node pairs of the iterator immediately after the pair
+  /// at `after`, preserving the order of the iterator.
+  ///
+  /// Returns [`InsertError::MissingAnchor`] if `after` does not exist, in
+  /// which case nothing is inserted. Otherwise, stops at the first item
+  /// whose key already exists, and returns [`InsertError::DuplicateKey`].
+  /// The pairs before the failed item are kept in the list, there is no
+  /// rollback.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the number of items.
+  pub fn insert_all_after_key<Q, I, T>(
+    &mut self,
+    after: &Q,
+    pairs: I,
+  ) -> Result<(), InsertError<K, T>>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+    I: IntoIterator<Item = (K, T)>,
+    T: Into<N>,
+  {
+    let anchor = match self.get_key_node(after) {
+      Some((k, _)) => k.clone(),
+      None => return Err(InsertError::MissingAnchor),
+    };
+    let mut cur = self.cursor_mut(anchor);
+    for (index, (key, node)) in pairs.into_iter().enumerate() {
+      cur
+        .insert_after(key, node)
+        .map_err(|(key, node)| InsertError::DuplicateKey { index, key, node })?;
+      cur.move_next();
+    }
+    Ok(())
+  }
+
+  /// Returns clones of all keys in list order.
+  ///
+  /// The returned keys can be passed to [`KeyNodeList::reorder`] to restore
+  /// the current order after reordering the list. Only the order is
+  /// restored, so this works only if the list still contains exactly the
+  /// same keys, otherwise [`KeyNodeList::reorder`] returns an error.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  #[inline]
+  pub fn snapshot_order(&self) -> Vec<K> {
+    self.keys().cloned().collect()
+  }
+
+  /// Relinks all key-node pairs of the list to match the given order.
+  ///
+  /// `order` must be a permutation of all keys in the list, otherwise
+  /// returns a [`ReorderError`] and the list is unchanged. Only the links
+  /// of the nodes are rewritten, nodes are neither moved nor rehashed.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn reorder(&mut self, order: &[K]) -> Result<(), ReorderError>
+  where
+    K: IndexKey,
+  {
+    if order.len() != self.len() {
+      return Err(ReorderError::LengthMismatch {
+        expected: self.len(),
+        found: order.len(),
+      });
+    }
+    let mut seen = KeyIndex::default();
+    for (i, k) in order.iter().enumerate() {
+      if !self.nodes.contains_key(k) {
+        return Err(ReorderError::UnknownKey(i));
+      }
+      if seen.insert(k, ()).is_some() {
+        return Err(ReorderError::DuplicateKey(i));
+      }
+    }
+    for (i, k) in order.iter().enumerate() {
+      let node = self.nodes.get_mut(k).unwrap();
+      *node_prev_mut!(node) = i.checked_sub(1).map(|i| order[i].clone());
+      *node_next_mut!(node) = order.get(i + 1).cloned();
+    }
+    self.head = order.first().cloned();
+    self.tail = order.last().cloned();
+    self.unlinked = 0;
+    self.record_ops(Op::Move, order.len());
+    Ok(())
+  }
+
+  /// Creates a list from the given map, and links the nodes in the given
+  /// order by [`KeyNodeList::reorder`].
+  ///
+  /// `order` must be a permutation of all keys in the map, otherwise
+  /// returns a [`ReorderError`].
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn from_map_with_order(map: M, order: &[K]) -> Result<Self, ReorderError>
+  where
+    K: IndexKey,
+  {
+    let mut list = Self::with_map(map);
+    list.reorder(order).map(|_| list)
+  }
+
+  /// Retains only the key-node pairs for which `f` returns `true`, visiting
+  /// the pairs in list order.
+  ///
+  /// The order of the retained pairs is unchanged.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    F: FnMut(&K, &N) -> bool,
+  {
+    let mut cur = self.cursor_front_mut();
+    while let (Some(k), Some(n)) = (cur.key(), cur.node()) {
+      if f(k, n) {
+        cur.move_next();
+      } else {
+        cur.remove_current();
+      }
+    }
+  }
+
+  /// Creates an iterator that visits the key-node pairs in list order, and
+  /// removes and yields the pairs for which `pred` returns `true`.
+  ///
+  /// Pairs are removed lazily as the iterator is advanced, the list is
+  /// valid after each step. If the iterator is dropped before it is
+  /// exhausted, the pairs not yet visited are kept in the list.
+  #[inline]
+  pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, N, M, F>
+  where
+    F: FnMut(&K, &N) -> bool,
+  {
+    ExtractIf {
+      cursor: self.cursor_front_mut(),
+      pred,
+    }
+  }
+
+  /// Retains only the key-node pairs for which `f` returns `true`, visiting
+  /// the pairs in list order, and then shrinks the capacity of the
+  /// underlying map as much as possible by [`KeyNodeList::shrink_to_fit`].
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn retain_and_shrink<F>(&mut self, f: F)
+  where
+    F: FnMut(&K, &N) -> bool,
+  {
+    self.retain(f);
+    self.shrink_to_fit();
+  }
+
+  /// Removes the longest run of leading key-node pairs for which `pred`
+  /// returns `true`, and returns them as a new list, preserving their order.
+  ///
+  /// The remaining pairs are left in the list, starting from the first pair
+  /// for which `pred` returns `false`.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the length of the returned list.
+  pub fn take_while_prefix<F>(&mut self, mut pred: F) -> Self
+  where
+    F: FnMut(&K, &N) -> bool,
+    M: Default,
+  {
+    let mut prefix = Self::new();
+    while let Some(key) = self.head.as_ref() {
+      if !pred(key, self.nodes.get(key).unwrap()) {
+        break;
+      }
+      let (key, node) = self.pop_front().unwrap();
+      prefix.push_back_unchecked(key, node);
+    }
+    prefix
+  }
+
+  /// Removes the key-node pair at the given key, and returns the previous
+  /// key, the removed pair and the next key, or returns `None` if `key`
+  /// does not exists.
+  ///
+  /// The previous and next keys are linked to each other after removal.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[allow(clippy::type_complexity)]
+  pub fn remove_with_neighbors<Q>(&mut self, key: &Q) -> Option<(Option<K>, (K, N), Option<K>)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.remove(key).map(|(k, n)| {
+      let prev = n.prev().cloned();
+      let next = n.next().cloned();
+      (prev, (k, n), next)
+    })
+  }
+
+  /// Moves all key-node pairs from `other` to the back of the list,
+  /// preserving their order, and leaves `other` empty.
+  ///
+  /// `other` must not share any key with the list, so appending a clone of
+  /// the list itself is an error. The keys are checked before anything is
+  /// moved: if some keys of `other` already exist in the list, returns a
+  /// [`KeyCollision`] error containing all of them in the order of `other`,
+  /// and both lists are unchanged.
+  ///
+  /// The pairs are moved between the underlying maps, but only the links
+  /// at the boundary are updated.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the length of `other`.
+  pub fn append(&mut self, other: &mut Self) -> Result<(), KeyCollision<K>> {
+    if !self.is_disjoint(other) {
+      let collisions = other.keys_in_both(self).cloned().collect();
+      return Err(KeyCollision(collisions));
+    }
+    let (head, tail) = (other.head.take(), other.tail.take());
+    let mut cur = head.clone();
+    let mut count = 0;
+    while let Some((k, n)) = cur.and_then(|k| other.nodes.remove_entry(&k)) {
+      cur = n.next().cloned();
+      count += 1;
+      let result = self.nodes.insert(k, n);
+      debug_assert!(result.is_ok(), "key collision after disjointness check");
+    }
+    // drop the unreachable nodes of `other`, if any
+    other.clear();
+    other.record_ops(Op::Pop, count);
+    self.record_ops(Op::Push, count);
+    if let Some(h) = head {
+      *node_prev_mut!(self, &h) = self.tail.clone();
+      match self.tail.take() {
+        Some(t) => *node_next_mut!(self, &t) = Some(h),
+        None => self.head = Some(h),
+      }
+      self.tail = tail;
+    }
+    Ok(())
+  }
+
+  /// Moves all key-node pairs from `other` to the back of the list,
+  /// preserving their order.
+  ///
+  /// For each key of `other` that already exists in the list (or that
+  /// collides with another key of `other`), `rename` is applied to obtain
+  /// a new key. If the new key still collides, `rename` is applied again
+  /// to the new key, until a unique key is found. So `rename` must
+  /// eventually produce a unique key, otherwise this method will never
+  /// return.
+  ///
+  /// Unlike [`KeyNodeList::append`], which rejects colliding keys, this
+  /// method renames them, so appending a clone of the list itself renames
+  /// every key of the clone. Use [`KeyNodeList::is_disjoint`] to check for
+  /// collisions in advance.
+  ///
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the length of `other`.
+  pub fn append_renaming<F>(&mut self, mut other: Self, mut rename: F)
+  where
+    F: FnMut(&K) -> K,
+  {
+    while let Some((mut key, node)) = other.pop_front() {
+      while self.contains_key(&key) || other.contains_key(&key) {
+        key = rename(&key);
+      }
+      self.push_back_unchecked(key, node);
+    }
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: MapIter<K, N>,
+{
+  /// Validates the links of all nodes and rebuilds the front and back keys
+  /// of the list from them.
+  ///
+  /// The links are valid if every link points to an existing node that
+  /// links back, there is exactly one front node (without a previous key),
+  /// and all nodes can be reached from the front node. Otherwise, returns a
+  /// [`LinkError`] and the front and back keys are unchanged.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn rebuild_from_links(&mut self) -> Result<(), LinkError<K>> {
+    let mut head = None;
+    let mut tail = None;
+    for (k, n) in self.nodes.iter() {
+      match n.prev() {
+        Some(p) => match self.nodes.get(p) {
+          Some(p) if p.next() == Some(k) => {}
+          Some(_) => return Err(LinkError::MismatchedLink(k.clone())),
+          None => return Err(LinkError::DanglingLink(k.clone())),
+        },
+        None if head.is_some() => return Err(LinkError::MultipleFronts),
+        None => head = Some(k),
+      }
+      match n.next() {
+        Some(x) => match self.nodes.get(x) {
+          Some(x) if x.prev() == Some(k) => {}
+          Some(_) => return Err(LinkError::MismatchedLink(k.clone())),
+          None => return Err(LinkError::DanglingLink(k.clone())),
+        },
+        None => tail = Some(k),
+      }
+    }
+    if head.is_none() && !self.is_empty() {
+      return Err(LinkError::NoFront);
+    }
+    // links are reciprocal, so walking from the front never revisits
+    // a node, and ends at the node without a next key
+    let mut count = 0;
+    let mut cur = head;
+    while let Some(k) = cur {
+      count += 1;
+      cur = self.nodes.get(k).and_then(|n| n.next());
+    }
+    if count != self.len() {
+      return Err(LinkError::Unreachable);
+    }
+    let (head, tail) = (head.cloned(), tail.cloned());
+    self.head = head;
+    self.tail = tail;
+    self.unlinked = 0;
+    self.bump_generation();
+    Ok(())
+  }
+
+  /// Installs `map` as the underlying map of the list, and rebuilds the
+  /// front and back keys from the links of its nodes by
+  /// [`KeyNodeList::rebuild_from_links`]. Any key-node pairs previously in
+  /// the list are dropped.
+  ///
+  /// If the nodes of `map` do not form a single chain, returns an error
+  /// containing the [`LinkError`] and `map`, and the list is left empty.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn restore_map(&mut self, map: M) -> Result<(), (LinkError<K>, M)>
+  where
+    M: Default,
+  {
+    self.nodes = map;
+    self.head = None;
+    self.tail = None;
+    self.current = None;
+    self.unlinked = 0;
+    self.bump_generation();
+    self
+      .rebuild_from_links()
+      .map_err(|e| (e, mem::take(&mut self.nodes)))
+  }
+}
+
+impl<K, V, M> KeyNodeList<K, ValueNode<K, V>, M>
+where
+  K: Hash + Eq,
+  M: Map<K, ValueNode<K, V>>,
 {
-  /// Returns `true` if the linked list contains a node for the specified key.
+  /// Searches a list whose values are sorted with a comparator function,
+  /// mirroring [`slice::binary_search_by`].
   ///
-  /// This operation should compute in *O*(1) time on average.
-  #[inline]
-  pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+  /// The comparator function should return an order code that indicates
+  /// whether its argument is `Less`, `Equal` or `Greater` the desired target.
+  ///
+  /// If a matching value is found then returns [`Ok`] containing the key of
+  /// the matching pair. If there are multiple matches, the first one in list
+  /// order is returned. If no match is found then returns [`Err`] containing
+  /// the index where a matching pair could be inserted while maintaining the
+  /// sorted order.
+  ///
+  /// Since the list can not be accessed randomly, this operation is actually
+  /// a linear scan, and should compute in *O*(n) time.
+  pub fn binary_search_by<F>(&self, mut f: F) -> Result<K, usize>
   where
-    K: Borrow<Q>,
-    Q: Hash + Eq,
+    K: Clone,
+    F: FnMut(&V) -> Ordering,
   {
-    self.nodes.contains_key(key)
+    for (i, (k, n)) in self.iter().enumerate() {
+      match f(n.value()) {
+        Ordering::Less => {}
+        Ordering::Equal => return Ok(k.clone()),
+        Ordering::Greater => return Err(i),
+      }
+    }
+    Err(self.len())
   }
 
-  /// Returns a reference to the node corresponding to the key,
-  /// or `None` if key does not exist.
+  /// Returns `true` if the two lists contain the same keys and values in
+  /// the same order.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  #[inline]
-  pub fn node<Q: ?Sized>(&self, key: &Q) -> Option<&N>
+  /// Unlike [`PartialEq`], which compares the underlying hash maps, this
+  /// method ignores the link fields of the nodes.
+  ///
+  /// This operation should compute in *O*(n) time.
+  pub fn values_eq(&self, other: &Self) -> bool
   where
-    K: Borrow<Q>,
-    Q: Hash + Eq,
+    V: PartialEq,
   {
-    self.nodes.get(key)
+    self.len() == other.len()
+      && self
+        .iter()
+        .zip(other.iter())
+        .all(|((k1, n1), (k2, n2))| k1 == k2 && n1.value() == n2.value())
   }
 
-  /// Returns a mutable reference to the node corresponding to the key,
-  /// or `None` if key does not exist.
+  /// Returns `true` if no value appears more than once in the list.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  #[inline]
-  pub fn node_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut N>
+  /// This operation should compute in *O*(n) time on average.
+  #[cfg(feature = "std")]
+  pub fn has_unique_values(&self) -> bool
   where
-    K: Borrow<Q>,
-    Q: Hash + Eq,
+    V: Hash + Eq,
   {
-    self.nodes.get_mut(key)
+    let mut seen = HashSet::with_capacity(self.len());
+    self.nodes().all(|n| seen.insert(n.value()))
   }
 
-  /// Returns a reference to the front node, or `None` if the list is empty.
+  /// Removes all but the first occurrence in list order of each value,
+  /// and returns the number of removed key-value pairs.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  #[inline]
-  pub fn front_node(&self) -> Option<&N> {
-    self.head.as_ref().and_then(|k| self.nodes.get(k))
+  /// This operation should compute in *O*(n) time on average.
+  #[cfg(feature = "std")]
+  pub fn remove_duplicate_values(&mut self) -> usize
+  where
+    K: Clone,
+    V: Hash + Eq,
+  {
+    let mut seen = HashSet::with_capacity(self.len());
+    let dups: Vec<_> = self
+      .iter()
+      .filter(|(_, n)| !seen.insert(n.value()))
+      .map(|(k, _)| k.clone())
+      .collect();
+    for k in &dups {
+      self.remove(k);
+    }
+    dups.len()
   }
 
-  /// Returns a mutable reference to the front node,
-  /// or `None` if the list is empty.
-  ///
-  /// This operation should compute in *O*(1) time on average.
+  /// Creates a consuming iterator over all values.
+  /// The list cannot be used after calling this.
+  /// The iterator element type is `V`.
   #[inline]
-  pub fn front_node_mut(&mut self) -> Option<&mut N> {
-    self.head.as_ref().and_then(|k| self.nodes.get_mut(k))
+  pub fn into_values(self) -> IntoValues<K, V, M>
+  where
+    K: Clone,
+  {
+    IntoValues {
+      iter: self.into_iter(),
+    }
   }
 
-  /// Returns a reference to the back node, or `None` if the list is empty.
+  /// Walks all adjacent key-value pairs in list order, and coalesces each
+  /// pair into one pair if `f` returns `Some(merged)`: the first pair keeps
+  /// its key and gets the value `merged`, and the second pair is removed.
+  /// The coalesced pair is then checked against its new next pair.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  #[inline]
-  pub fn back_node(&self) -> Option<&N> {
-    self.tail.as_ref().and_then(|k| self.nodes.get(k))
+  /// This operation should compute in *O*(n) time on average.
+  pub fn coalesce<F>(&mut self, mut f: F)
+  where
+    K: Clone,
+    F: FnMut(&K, &V, &K, &V) -> Option<V>,
+  {
+    let mut cur = self.head.clone();
+    while let Some(key) = cur {
+      let node = self.nodes.get(&key).unwrap();
+      let next = match node.next() {
+        Some(next) => next.clone(),
+        None => break,
+      };
+      let next_node = self.nodes.get(&next).unwrap();
+      match f(&key, node.value(), &next, next_node.value()) {
+        Some(merged) => {
+          self.remove::<K>(&next);
+          *self.nodes.get_mut(&key).unwrap().value_mut() = merged;
+          cur = Some(key);
+        }
+        None => cur = Some(next),
+      }
+    }
   }
 
-  /// Returns a mutable reference to the back node,
-  /// or `None` if the list is empty.
-  ///
-  /// This operation should compute in *O*(1) time on average.
+  /// Returns an iterator over all maximal runs of consecutive equal values,
+  /// in list order. Each item contains the first value of the run and the
+  /// keys in the run.
+  /// The iterator element type is `(&'a V, Vec<&'a K>)`.
   #[inline]
-  pub fn back_node_mut(&mut self) -> Option<&mut N> {
-    self.tail.as_ref().and_then(|k| self.nodes.get_mut(k))
+  pub fn group_runs(&self) -> impl Iterator<Item = (&V, Vec<&K>)>
+  where
+    V: PartialEq,
+  {
+    self.group_runs_by(|v| v)
   }
 
-  /// Provides a cursor at the specific key.
+  /// Returns an iterator over all maximal runs of consecutive values whose
+  /// projections by `f` are equal, in list order. Each item contains the
+  /// first value of the run and the keys in the run.
+  /// The iterator element type is `(&'a V, Vec<&'a K>)`.
   ///
-  /// The cursor is pointing to the null pair if the key does not exist.
-  #[inline]
-  pub fn cursor(&self, key: K) -> Cursor<K, N, M> {
-    Cursor {
-      list: self,
-      key: self.contains_key(&key).then_some(key),
-    }
+  /// The list is walked only once, `f` is called once for each value.
+  pub fn group_runs_by<'a, F, T>(&'a self, mut f: F) -> impl Iterator<Item = (&'a V, Vec<&'a K>)>
+  where
+    F: FnMut(&'a V) -> T + 'a,
+    T: PartialEq + 'a,
+  {
+    let mut iter = self.iter();
+    let mut pending = iter.next().map(|(k, n)| (k, n.value(), f(n.value())));
+    iter::from_fn(move || {
+      let (key, value, group) = pending.take()?;
+      let mut keys = Vec::from([key]);
+      for (k, n) in iter.by_ref() {
+        let g = f(n.value());
+        if g != group {
+          pending = Some((k, n.value(), g));
+          break;
+        }
+        keys.push(k);
+      }
+      Some((value, keys))
+    })
   }
 
-  /// Provides a cursor with editing operations at the specific key.
+  /// Returns the key and value of the maximum value, or `None` if the list
+  /// is empty. If several values are equally maximum, the first one in list
+  /// order is returned.
   ///
-  /// The cursor is pointing to the null pair if the key does not exist.
+  /// This operation should compute in *O*(n) time on average.
   #[inline]
-  pub fn cursor_mut(&mut self, key: K) -> CursorMut<K, N, M> {
-    CursorMut {
-      key: self.contains_key(&key).then_some(key),
-      list: self,
-    }
+  pub fn max_by_value(&self) -> Option<(&K, &V)>
+  where
+    V: Ord,
+  {
+    self.max_by(Ord::cmp)
   }
-}
 
-impl<K, N, M> KeyNodeList<K, N, M>
-where
-  K: Hash + Eq + Clone,
-  M: Map<K, N>,
-{
-  /// Provides a cursor at the front key-node pair.
+  /// Returns the key and value of the minimum value, or `None` if the list
+  /// is empty. If several values are equally minimum, the first one in list
+  /// order is returned.
   ///
-  /// The cursor is pointing to the null pair if the list is empty.
+  /// This operation should compute in *O*(n) time on average.
   #[inline]
-  pub fn cursor_front(&self) -> Cursor<K, N, M> {
-    Cursor {
-      list: self,
-      key: self.head.clone(),
-    }
+  pub fn min_by_value(&self) -> Option<(&K, &V)>
+  where
+    V: Ord,
+  {
+    self.min_by(Ord::cmp)
   }
 
-  /// Provides a cursor with editing operations at the front key-node pair.
+  /// Returns the key and value of the maximum value with respect to the
+  /// comparison function `compare`, or `None` if the list is empty. If
+  /// several values are equally maximum, the first one in list order is
+  /// returned.
   ///
-  /// The cursor is pointing to the null pair if the list is empty.
-  #[inline]
-  pub fn cursor_front_mut(&mut self) -> CursorMut<K, N, M> {
-    CursorMut {
-      key: self.head.clone(),
-      list: self,
-    }
+  /// This operation should compute in *O*(n) time on average.
+  pub fn max_by<F>(&self, mut compare: F) -> Option<(&K, &V)>
+  where
+    F: FnMut(&V, &V) -> Ordering,
+  {
+    self
+      .iter()
+      .map(|(k, n)| (k, n.value()))
+      .reduce(|a, b| match compare(b.1, a.1) {
+        Ordering::Greater => b,
+        _ => a,
+      })
   }
 
-  /// Provides a cursor at the back key-node pair.
+  /// Returns the key and value of the minimum value with respect to the
+  /// comparison function `compare`, or `None` if the list is empty. If
+  /// several values are equally minimum, the first one in list order is
+  /// returned.
   ///
-  /// The cursor is pointing to the null pair if the list is empty.
-  #[inline]
-  pub fn cursor_back(&self) -> Cursor<K, N, M> {
-    Cursor {
-      list: self,
-      key: self.tail.clone(),
-    }
+  /// This operation should compute in *O*(n) time on average.
+  pub fn min_by<F>(&self, mut compare: F) -> Option<(&K, &V)>
+  where
+    F: FnMut(&V, &V) -> Ordering,
+  {
+    self
+      .iter()
+      .map(|(k, n)| (k, n.value()))
+      .reduce(|a, b| match compare(b.1, a.1) {
+        Ordering::Less => b,
+        _ => a,
+      })
   }
 
-  /// Provides a cursor with editing operations at the back key-node pair.
+  /// Consumes the list, and converts it into a [`LinkedList`] of key-value
+  /// pairs in list order.
   ///
-  /// The cursor is pointing to the null pair if the list is empty.
-  #[inline]
-  pub fn cursor_back_mut(&mut self) -> CursorMut<K, N, M> {
-    CursorMut {
-      key: self.tail.clone(),
-      list: self,
-    }
+  /// This operation should compute in *O*(n) time on average.
+  #[cfg(feature = "std")]
+  pub fn into_linked_list(self) -> LinkedList<(K, V)>
+  where
+    K: Clone,
+  {
+    self.into_iter().map(|(k, n)| (k, n.into_value())).collect()
   }
-}
 
-impl<K, N, M> KeyNodeList<K, N, M>
-where
-  K: Hash + Eq + Clone,
-  N: Node<Key = K>,
-  M: Map<K, N>,
-{
-  /// Creates a consuming iterator over all keys.
-  /// The list cannot be used after calling this.
-  /// The iterator element type is `K`.
-  #[inline]
-  pub fn into_keys(self) -> IntoKeys<K, N, M> {
-    IntoKeys {
-      iter: self.into_iter(),
-    }
+  /// Creates a list from a [`LinkedList`] of key-value pairs, preserving
+  /// the order.
+  ///
+  /// Returns a [`DuplicateKey`] error containing the first key that appears
+  /// more than once.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  #[cfg(feature = "std")]
+  pub fn from_linked_list(list: LinkedList<(K, V)>) -> Result<Self, DuplicateKey<K>>
+  where
+    K: Clone,
+    M: Default,
+  {
+    Self::from_entries_in_order(list.into_iter().map(|(k, v)| (k, v.into())))
   }
 
-  /// Creates a consuming iterator over all nodes.
-  /// The list cannot be used after calling this.
-  /// The iterator element type is `N`.
-  #[inline]
-  pub fn into_nodes(self) -> IntoNodes<K, N, M> {
-    IntoNodes {
-      iter: self.into_iter(),
+  /// Inserts a key-value pair into the list, keeping the values in
+  /// non-decreasing order, assuming that the list is already sorted by
+  /// values.
+  ///
+  /// The insertion is stable: the new pair is inserted after all existing
+  /// pairs with equal values.
+  ///
+  /// If `key` already exists, returns an error containing `key` and `value`.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn insert_sorted(&mut self, key: K, value: V) -> Result<(), (K, V)>
+  where
+    K: Clone,
+    V: Ord,
+  {
+    if self.contains_key(&key) {
+      return Err((key, value));
+    }
+    let mut cur = self.cursor_front_mut();
+    while cur.node().is_some_and(|n| n.value() <= &value) {
+      cur.move_next();
     }
+    cur.insert_before(key, value)
   }
 
-  /// Adds a key-node pair first in the list.
+  /// Creates a list from an iterator of key-value pairs. On a duplicate
+  /// key, the larger value is kept and the other is discarded.
   ///
-  /// If `key` already exists, returns an error containing `key` and `node`.
+  /// Keys are ordered by their first appearance, updating the value of an
+  /// existing key does not move its position.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  pub fn push_front<T: Into<N>>(&mut self, key: K, node: T) -> Result<(), (K, T)> {
-    self.nodes.insert(key.clone(), node).map(|_| {
-      let next = self.head.replace(key.clone());
-      match &next {
-        Some(k) => *node_prev_mut!(self, k) = Some(key.clone()),
-        None => self.tail = Some(key.clone()),
-      }
-      let node = self.node_mut(&key).unwrap();
-      *node_prev_mut!(node) = None;
-      *node_next_mut!(node) = next;
-    })
+  /// This operation should compute in *O*(n) time on average.
+  pub fn from_iter_keeping_max<I>(iter: I) -> Self
+  where
+    K: Clone,
+    V: Ord,
+    M: Default,
+    I: IntoIterator<Item = (K, V)>,
+  {
+    Self::from_iter_keeping(iter, |old, new| new > old)
   }
 
-  /// Adds a key-node pair back in the list.
+  /// Creates a list from an iterator of key-value pairs. On a duplicate
+  /// key, the smaller value is kept and the other is discarded.
   ///
-  /// If `key` already exists, returns an error containing `key` and `node`.
+  /// Keys are ordered by their first appearance, updating the value of an
+  /// existing key does not move its position.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  pub fn push_back<T: Into<N>>(&mut self, key: K, node: T) -> Result<(), (K, T)> {
-    self.nodes.insert(key.clone(), node).map(|_| {
-      let prev = self.tail.replace(key.clone());
-      match &prev {
-        Some(k) => *node_next_mut!(self, k) = Some(key.clone()),
-        None => self.head = Some(key.clone()),
+  /// This operation should compute in *O*(n) time on average.
+  pub fn from_iter_keeping_min<I>(iter: I) -> Self
+  where
+    K: Clone,
+    V: Ord,
+    M: Default,
+    I: IntoIterator<Item = (K, V)>,
+  {
+    Self::from_iter_keeping(iter, |old, new| new < old)
+  }
+
+  /// Creates a list from an iterator of key-value pairs. On a duplicate
+  /// key, the value is replaced if `replace(old, new)` returns `true`.
+  fn from_iter_keeping<I, F>(iter: I, replace: F) -> Self
+  where
+    K: Clone,
+    M: Default,
+    I: IntoIterator<Item = (K, V)>,
+    F: Fn(&V, &V) -> bool,
+  {
+    let mut list = Self::new();
+    for (key, value) in iter {
+      match list.node_mut(&key) {
+        Some(n) => {
+          if replace(n.value(), &value) {
+            *n.value_mut() = value;
+          }
+        }
+        None => list.push_back_unchecked(key, value),
       }
-      let node = self.node_mut(&key).unwrap();
-      *node_prev_mut!(node) = prev;
-      *node_next_mut!(node) = None;
-    })
+    }
+    list
   }
 
-  /// Adds a key first in the list.
+  /// Returns all keys and values with their indices in list order.
   ///
-  /// If `key` already exists, returns an error containing `key`.
+  /// This operation should compute in *O*(n) time on average.
+  pub fn to_indexed_pairs(&self) -> Vec<(usize, &K, &V)> {
+    self
+      .iter()
+      .enumerate()
+      .map(|(i, (k, n))| (i, k, n.value()))
+      .collect()
+  }
+
+  /// Returns a mutable reference to the value of the given key. If the key
+  /// does not exist, a key-value pair with the default value is pushed to
+  /// the back of the list first.
   ///
   /// This operation should compute in *O*(1) time on average.
   #[inline]
-  pub fn push_key_front(&mut self, key: K) -> Result<(), K>
+  pub fn get_or_insert_default_back(&mut self, key: K) -> &mut V
   where
-    (): Into<N>,
+    K: Clone,
+    V: Default,
   {
-    self.push_front(key, ()).map_err(|(k, _)| k)
+    self.entry(key).or_insert_back(V::default()).value_mut()
   }
 
-  /// Adds a key back in the list.
-  ///
-  /// If `key` already exists, returns an error containing `key`.
+  /// Returns a mutable reference to the value of the given key. If the key
+  /// does not exist, a key-value pair with the default value is pushed to
+  /// the front of the list first.
   ///
   /// This operation should compute in *O*(1) time on average.
   #[inline]
-  pub fn push_key_back(&mut self, key: K) -> Result<(), K>
+  pub fn get_or_insert_default_front(&mut self, key: K) -> &mut V
   where
-    (): Into<N>,
+    K: Clone,
+    V: Default,
   {
-    self.push_back(key, ()).map_err(|(k, _)| k)
+    self.entry(key).or_insert_front(V::default()).value_mut()
   }
 
-  /// Removes the first key-node pair and returns it, or `None` if the list
-  /// is empty.
+  /// Applies the given key-value updates to the list.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  pub fn pop_front(&mut self) -> Option<(K, N)> {
-    self.head.take().map(|k| {
-      let node = self.nodes.remove(&k).unwrap();
-      self.head = node.next().cloned();
-      (k, node)
-    })
-  }
-
-  /// Removes the last key-node pair and returns it, or `None` if the list
-  /// is empty.
+  /// For each update, if the key already exists, the value is overwritten
+  /// in place and the position of the pair is unchanged. Otherwise, a new
+  /// key-value pair is pushed to the back of the list.
   ///
-  /// This operation should compute in *O*(1) time on average.
-  pub fn pop_back(&mut self) -> Option<(K, N)> {
-    self.tail.take().map(|k| {
-      let node = self.nodes.remove(&k).unwrap();
-      self.tail = node.prev().cloned();
-      (k, node)
-    })
-  }
-
-  /// Removes the key-node pair at the given key and returns it,
-  /// or returns `None` if `key` does not exists.
-  pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, N)>
+  /// This operation should compute in *O*(n) time on average, where *n* is
+  /// the number of updates.
+  pub fn apply_updates<I>(&mut self, updates: I)
   where
-    K: Borrow<Q>,
-    Q: Hash + Eq,
+    K: Clone,
+    I: IntoIterator<Item = (K, V)>,
   {
-    self.nodes.remove_entry(key).map(|(k, n)| {
-      match n.prev() {
-        Some(k) => *node_next_mut!(self, k) = n.next().cloned(),
-        None => self.head = n.next().cloned(),
-      }
-      match n.next() {
-        Some(k) => *node_prev_mut!(self, k) = n.prev().cloned(),
-        None => self.tail = n.prev().cloned(),
+    for (key, value) in updates {
+      match self.node_mut(&key) {
+        Some(node) => *node.value_mut() = value,
+        None => self.push_back_unchecked(key, value),
       }
-      (k, n)
-    })
+    }
+  }
+}
+
+impl<K, V, M> KeyNodeList<K, ValueNode<K, V>, M>
+where
+  K: IndexKey,
+  M: MapIter<K, ValueNode<K, V>>,
+{
+  /// Rotates the values in list order by `n` positions to the left, so the
+  /// value of each key moves to the key `n` positions earlier (wrapping
+  /// around). The keys and the links stay unchanged.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn rotate_values_left(&mut self, n: usize) {
+    let mut values: Vec<_> = self.nodes_mut().map(ValueNode::value_mut).collect();
+    if values.is_empty() {
+      return;
+    }
+    // rotate by three reversals, values are swapped in place
+    let mid = n % values.len();
+    reverse_values(&mut values[..mid]);
+    reverse_values(&mut values[mid..]);
+    reverse_values(&mut values);
   }
 }
 
@@ -405,6 +2632,23 @@ where
   }
 }
 
+/// Formats the keys of a [`KeyNodeList`], returned by
+/// [`KeyNodeList::debug_keys`].
+struct DebugKeys<'a, K, N, M> {
+  list: &'a KeyNodeList<K, N, M>,
+}
+
+impl<K, N, M> fmt::Debug for DebugKeys<'_, K, N, M>
+where
+  K: Hash + Eq + fmt::Debug,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_list().entries(self.list.keys()).finish()
+  }
+}
+
 impl<K, N, M> Default for KeyNodeList<K, N, M>
 where
   M: Default,
@@ -415,6 +2659,13 @@ where
       nodes: M::default(),
       head: None,
       tail: None,
+      current: None,
+      unlinked: 0,
+      generation: 0,
+      auto_shrink: None,
+      last_shrink: None,
+      #[cfg(feature = "metrics")]
+      stats: OpStats::default(),
       phantom: PhantomData,
     }
   }
@@ -445,9 +2696,9 @@ where
   /// # Example
   ///
   /// ```
-  /// use key_node_list::KeyValueList;
+  /// use key_node_list::BTreeValueList;
   ///
-  /// let mut list: KeyValueList<i32, ()> = KeyValueList::new();
+  /// let mut list: BTreeValueList<i32, ()> = BTreeValueList::new();
   /// list.extend(&[1, 2, 3]);
   /// assert_eq!(list.front_key(), Some(&1));
   /// assert_eq!(list.back_key(), Some(&3));
@@ -484,9 +2735,9 @@ where
   /// # Example
   ///
   /// ```
-  /// use key_node_list::KeyValueList;
+  /// use key_node_list::BTreeValueList;
   ///
-  /// let mut list: KeyValueList<i32, ()> = KeyValueList::new();
+  /// let mut list: BTreeValueList<i32, ()> = BTreeValueList::new();
   /// list.extend([1, 2, 3]);
   /// assert_eq!(list.front_key(), Some(&1));
   /// assert_eq!(list.back_key(), Some(&3));
@@ -537,9 +2788,9 @@ where
   /// # Example
   ///
   /// ```
-  /// use key_node_list::KeyValueList;
+  /// use key_node_list::BTreeValueList;
   ///
-  /// let list: KeyValueList<i32, ()> = [1, 2, 3].into_iter().collect();
+  /// let list: BTreeValueList<i32, ()> = [1, 2, 3].into_iter().collect();
   /// assert_eq!(list.front_key(), Some(&1));
   /// assert_eq!(list.back_key(), Some(&3));
   /// ```
@@ -554,7 +2805,7 @@ impl<'a, K, Q, N, M> Index<&'a Q> for KeyNodeList<K, N, M>
 where
   K: Hash + Eq + Borrow<Q>,
   Q: ?Sized + Hash + Eq,
-  M: Map<K, N>,
+  M: Map<K, N> + MapLookup<K, N, Q>,
 {
   type Output = N;
 
@@ -607,3 +2858,12 @@ where
 }
 
 impl<K, N, M> Eq for KeyNodeList<K, N, M> where M: PartialEq {}
+
+/// Reverses the values referenced by the given slice.
+fn reverse_values<V>(values: &mut [&mut V]) {
+  let len = values.len();
+  for i in 0..len / 2 {
+    let (front, back) = values.split_at_mut(len - 1 - i);
+    mem::swap(&mut *front[i], &mut *back[0]);
+  }
+}
@@ -1,13 +1,20 @@
-use std::borrow::Borrow;
-use std::collections::HashMap;
-use std::hash::Hash;
+use alloc::collections::{btree_map, BTreeMap, TryReserveError};
+use core::borrow::Borrow;
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::{hash_map, HashMap};
 
 /// An interface to the hash map operations used by
 /// [`KeyNodeList`](crate::KeyNodeList).
 ///
 /// Any data structure that implements this trait can be used as the
 /// underlying hash map for [`KeyNodeList`](crate::KeyNodeList).
-pub trait Map<K, V> {
+///
+/// Lookups are provided by the supertrait [`MapLookup`], which is generic
+/// over the borrowed form of the key, so that a map can put its own bounds
+/// on the key type, such as [`Ord`] for [`BTreeMap`]. Maps that only need
+/// [`Hash`] and [`Eq`], such as `HashMap`, cost their users nothing more.
+pub trait Map<K, V>: MapLookup<K, V> {
   /// Returns the number of elements in the map.
   ///
   /// This operation should compute in *O*(1) time.
@@ -22,17 +29,69 @@ pub trait Map<K, V> {
   }
 
   /// Clears the map, removing all key-value pairs.
-  /// Keeps the allocated memory for reuse.
+  ///
+  /// Implementations should keep the allocated memory for reuse, which
+  /// [`KeyNodeList::reset`](crate::KeyNodeList::reset) relies on.
   fn clear(&mut self);
 
-  /// Returns `true` if the map contains a value for the specified key.
+  /// Inserts a key-value pair into the map.
+  ///
+  /// If the map did have this key present, returns an error containing the
+  /// key and the value.
+  ///
+  /// If the map did not have this key present, the key-value pair is
+  /// inserted, and [`Ok(())`](Ok) is returned.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  fn insert<T: Into<V>>(&mut self, k: K, v: T) -> Result<(), (K, T)>
+  where
+    K: Hash + Eq;
+
+  /// Returns the number of elements the map can hold without reallocating.
+  ///
+  /// The default implementation returns [`Map::len`].
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  fn capacity(&self) -> usize {
+    self.len()
+  }
+
+  /// Reserves capacity for at least `additional` more elements to be
+  /// inserted in the map.
   ///
-  /// The key may be any borrowed form of the map’s key type, but [`Hash`]
-  /// and [`Eq`] on the borrowed form must match those for the key type.
+  /// The default implementation does nothing.
+  #[inline]
+  fn reserve(&mut self, additional: usize)
+  where
+    K: Hash + Eq,
+  {
+    let _ = additional;
+  }
+
+  /// Shrinks the capacity of the map as much as possible.
+  ///
+  /// The default implementation does nothing.
+  #[inline]
+  fn shrink_to_fit(&mut self)
+  where
+    K: Hash + Eq,
+  {
+  }
+}
+
+/// Lookups in a map by `Q`, a borrowed form of the key type `K`.
+///
+/// The key may be any borrowed form of the map’s key type, but [`Hash`]
+/// and [`Eq`] on the borrowed form must match those for the key type, as
+/// must any other trait the map compares keys with, such as [`Ord`] for
+/// [`BTreeMap`]. Every [`Map<K, V>`](Map) can be looked up by `K` itself.
+pub trait MapLookup<K, V, Q: ?Sized = K> {
+  /// Returns `true` if the map contains a value for the specified key.
   ///
   /// This operation should compute in *O*(1) time on average.
   #[inline]
-  fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
+  fn contains_key(&self, k: &Q) -> bool
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq,
@@ -42,48 +101,34 @@ pub trait Map<K, V> {
 
   /// Returns a reference to the value corresponding to the key.
   ///
-  /// The key may be any borrowed form of the map’s key type, but [`Hash`]
-  /// and [`Eq`] on the borrowed form must match those for the key type.
-  ///
   /// This operation should compute in *O*(1) time on average.
-  fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
+  fn get(&self, k: &Q) -> Option<&V>
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq;
 
   /// Returns a mutable reference to the value corresponding to the key.
   ///
-  /// The key may be any borrowed form of the map’s key type, but [`Hash`]
-  /// and [`Eq`] on the borrowed form must match those for the key type.
-  ///
   /// This operation should compute in *O*(1) time on average.
-  fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+  fn get_mut(&mut self, k: &Q) -> Option<&mut V>
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq;
 
-  /// Inserts a key-value pair into the map.
-  ///
-  /// If the map did have this key present, returns an error containing the
-  /// key and the value.
-  ///
-  /// If the map did not have this key present, the key-value pair is
-  /// inserted, and [`Ok(())`](Ok) is returned.
+  /// Returns the key-value pair corresponding to the supplied key.
   ///
   /// This operation should compute in *O*(1) time on average.
-  fn insert<T: Into<V>>(&mut self, k: K, v: T) -> Result<(), (K, T)>
+  fn get_key_value(&self, k: &Q) -> Option<(&K, &V)>
   where
-    K: Hash + Eq;
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq;
 
   /// Removes a key from the map, returning the value at the key if the key
   /// was previously in the map.
   ///
-  /// The key may be any borrowed form of the map’s key type, but [`Hash`]
-  /// and [`Eq`] on the borrowed form must match those for the key type.
-  ///
   /// This operation should compute in *O*(1) time on average.
   #[inline]
-  fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<V>
+  fn remove(&mut self, k: &Q) -> Option<V>
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq,
@@ -94,16 +139,14 @@ pub trait Map<K, V> {
   /// Removes a key from the map, returning the stored key and value if the
   /// key was previously in the map.
   ///
-  /// The key may be any borrowed form of the map’s key type, but [`Hash`]
-  /// and [`Eq`] on the borrowed form must match those for the key type.
-  ///
   /// This operation should compute in *O*(1) time on average.
-  fn remove_entry<Q: ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+  fn remove_entry(&mut self, k: &Q) -> Option<(K, V)>
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq;
 }
 
+#[cfg(feature = "std")]
 impl<K, V> Map<K, V> for HashMap<K, V> {
   #[inline]
   fn len(&self) -> usize {
@@ -116,7 +159,45 @@ impl<K, V> Map<K, V> for HashMap<K, V> {
   }
 
   #[inline]
-  fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
+  #[allow(clippy::map_entry)]
+  fn insert<T: Into<V>>(&mut self, k: K, v: T) -> Result<(), (K, T)>
+  where
+    K: Hash + Eq,
+  {
+    if self.contains_key(&k) {
+      Err((k, v))
+    } else {
+      self.insert(k, v.into());
+      Ok(())
+    }
+  }
+
+  #[inline]
+  fn capacity(&self) -> usize {
+    self.capacity()
+  }
+
+  #[inline]
+  fn reserve(&mut self, additional: usize)
+  where
+    K: Hash + Eq,
+  {
+    self.reserve(additional)
+  }
+
+  #[inline]
+  fn shrink_to_fit(&mut self)
+  where
+    K: Hash + Eq,
+  {
+    self.shrink_to_fit()
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, Q: ?Sized> MapLookup<K, V, Q> for HashMap<K, V> {
+  #[inline]
+  fn get(&self, k: &Q) -> Option<&V>
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq,
@@ -125,7 +206,7 @@ impl<K, V> Map<K, V> for HashMap<K, V> {
   }
 
   #[inline]
-  fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+  fn get_mut(&mut self, k: &Q) -> Option<&mut V>
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq,
@@ -133,6 +214,39 @@ impl<K, V> Map<K, V> for HashMap<K, V> {
     self.get_mut(k)
   }
 
+  #[inline]
+  fn get_key_value(&self, k: &Q) -> Option<(&K, &V)>
+  where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq,
+  {
+    self.get_key_value(k)
+  }
+
+  #[inline]
+  fn remove_entry(&mut self, k: &Q) -> Option<(K, V)>
+  where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq,
+  {
+    self.remove_entry(k)
+  }
+}
+
+impl<K, V> Map<K, V> for BTreeMap<K, V>
+where
+  K: Ord,
+{
+  #[inline]
+  fn len(&self) -> usize {
+    self.len()
+  }
+
+  #[inline]
+  fn clear(&mut self) {
+    self.clear()
+  }
+
   #[inline]
   #[allow(clippy::map_entry)]
   fn insert<T: Into<V>>(&mut self, k: K, v: T) -> Result<(), (K, T)>
@@ -146,9 +260,42 @@ impl<K, V> Map<K, V> for HashMap<K, V> {
       Ok(())
     }
   }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for BTreeMap<K, V>
+where
+  K: Ord + Borrow<Q>,
+  Q: ?Sized + Ord,
+{
+  #[inline]
+  fn get(&self, k: &Q) -> Option<&V>
+  where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq,
+  {
+    self.get(k)
+  }
+
+  #[inline]
+  fn get_mut(&mut self, k: &Q) -> Option<&mut V>
+  where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq,
+  {
+    self.get_mut(k)
+  }
 
   #[inline]
-  fn remove_entry<Q: ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+  fn get_key_value(&self, k: &Q) -> Option<(&K, &V)>
+  where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq,
+  {
+    self.get_key_value(k)
+  }
+
+  #[inline]
+  fn remove_entry(&mut self, k: &Q) -> Option<(K, V)>
   where
     K: Hash + Eq + Borrow<Q>,
     Q: Hash + Eq,
@@ -156,3 +303,143 @@ impl<K, V> Map<K, V> for HashMap<K, V> {
     self.remove_entry(k)
   }
 }
+
+/// An extension of [`Map`] for the maps that can iterate over their
+/// key-value pairs.
+pub trait MapIter<K, V>: Map<K, V> {
+  /// Iterator type of [`MapIter::iter`].
+  type Iter<'a>: Iterator<Item = (&'a K, &'a V)>
+  where
+    Self: 'a,
+    K: 'a,
+    V: 'a;
+
+  /// Iterator type of [`MapIter::iter_mut`].
+  type IterMut<'a>: Iterator<Item = (&'a K, &'a mut V)>
+  where
+    Self: 'a,
+    K: 'a,
+    V: 'a;
+
+  /// Returns an iterator visiting all key-value pairs in arbitrary order.
+  ///
+  /// The yielded values must be the same values that [`MapLookup::get`] returns
+  /// for the corresponding keys.
+  fn iter(&self) -> Self::Iter<'_>;
+
+  /// Returns an iterator visiting all key-value pairs in arbitrary order,
+  /// with mutable references to the values.
+  ///
+  /// The yielded values must be the same values that [`MapLookup::get_mut`]
+  /// returns for the corresponding keys.
+  fn iter_mut(&mut self) -> Self::IterMut<'_>;
+}
+
+#[cfg(feature = "std")]
+impl<K, V> MapIter<K, V> for HashMap<K, V> {
+  type Iter<'a>
+    = hash_map::Iter<'a, K, V>
+  where
+    K: 'a,
+    V: 'a;
+
+  type IterMut<'a>
+    = hash_map::IterMut<'a, K, V>
+  where
+    K: 'a,
+    V: 'a;
+
+  #[inline]
+  fn iter(&self) -> Self::Iter<'_> {
+    self.iter()
+  }
+
+  #[inline]
+  fn iter_mut(&mut self) -> Self::IterMut<'_> {
+    self.iter_mut()
+  }
+}
+
+impl<K, V> MapIter<K, V> for BTreeMap<K, V>
+where
+  K: Ord,
+{
+  type Iter<'a>
+    = btree_map::Iter<'a, K, V>
+  where
+    K: 'a,
+    V: 'a;
+
+  type IterMut<'a>
+    = btree_map::IterMut<'a, K, V>
+  where
+    K: 'a,
+    V: 'a;
+
+  #[inline]
+  fn iter(&self) -> Self::Iter<'_> {
+    self.iter()
+  }
+
+  #[inline]
+  fn iter_mut(&mut self) -> Self::IterMut<'_> {
+    self.iter_mut()
+  }
+}
+
+/// A map from keys to other data, used by the list for bookkeeping when
+/// the underlying map can not be used. It is a hash map if the `std`
+/// feature is enabled, or a [`BTreeMap`] otherwise.
+#[cfg(feature = "std")]
+pub(crate) type KeyIndex<K, V> = HashMap<K, V>;
+#[cfg(not(feature = "std"))]
+pub(crate) type KeyIndex<K, V> = BTreeMap<K, V>;
+
+/// Keys of the indices that some operations of
+/// [`KeyNodeList`](crate::KeyNodeList) build in addition to the underlying
+/// map.
+///
+/// With the `std` feature, the indices are hash maps, and this trait is
+/// implemented for all keys that implement [`Hash`] and [`Eq`]. Otherwise,
+/// the keys must also implement [`Ord`].
+#[cfg(feature = "std")]
+pub trait IndexKey: Hash + Eq {}
+
+#[cfg(feature = "std")]
+impl<K: ?Sized + Hash + Eq> IndexKey for K {}
+
+/// Keys of the indices that some operations of
+/// [`KeyNodeList`](crate::KeyNodeList) build in addition to the underlying
+/// map.
+///
+/// With the `std` feature, the indices are hash maps, and this trait is
+/// implemented for all keys that implement [`Hash`] and [`Eq`]. Otherwise,
+/// the keys must also implement [`Ord`].
+#[cfg(not(feature = "std"))]
+pub trait IndexKey: Hash + Ord {}
+
+#[cfg(not(feature = "std"))]
+impl<K: ?Sized + Hash + Ord> IndexKey for K {}
+
+/// An extension of [`Map`] for the maps that can manage their capacity.
+pub trait MapCapacity<K, V>: Map<K, V> {
+  /// Tries to reserve capacity for at least `additional` more elements to
+  /// be inserted in the map.
+  ///
+  /// If the capacity overflows, or the allocator reports a failure,
+  /// then an error is returned.
+  fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError>
+  where
+    K: Hash + Eq;
+}
+
+#[cfg(feature = "std")]
+impl<K, V> MapCapacity<K, V> for HashMap<K, V> {
+  #[inline]
+  fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError>
+  where
+    K: Hash + Eq,
+  {
+    self.try_reserve(additional)
+  }
+}
@@ -0,0 +1,60 @@
+use crate::list::{KeyNodeList, Op};
+
+/// Counts of structural operations performed on a [`KeyNodeList`],
+/// returned by [`KeyNodeList::op_stats`].
+///
+/// Bulk operations are counted per pair. Each pair moved by
+/// [`KeyNodeList::append`] or by splitting a list, such as
+/// [`KeyNodeList::split_off`], counts as a pop from the source list and a
+/// push to the target list, and [`KeyNodeList::reorder`] counts a move for
+/// each pair. Operations that drop, take or install the whole map at once,
+/// such as [`KeyNodeList::clear`], [`KeyNodeList::take_map`],
+/// [`KeyNodeList::restore_map`] and [`KeyNodeList::unlink_all`], are not
+/// counted.
+///
+/// This structure is only available when the `metrics` feature is enabled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OpStats {
+  /// Number of key-node pairs pushed to the front or back of the list.
+  pub pushes: usize,
+  /// Number of key-node pairs popped from the front or back of the list.
+  pub pops: usize,
+  /// Number of key-node pairs inserted by cursors.
+  pub inserts: usize,
+  /// Number of key-node pairs removed by keys or cursors.
+  pub removes: usize,
+  /// Number of key-node pairs moved to other positions of the list.
+  pub moves: usize,
+}
+
+impl OpStats {
+  /// Records the given operation performed on `count` pairs.
+  #[inline]
+  pub(crate) fn record(&mut self, op: Op, count: usize) {
+    match op {
+      Op::Push => self.pushes += count,
+      Op::Pop => self.pops += count,
+      Op::Insert => self.inserts += count,
+      Op::Remove => self.removes += count,
+      Op::Move => self.moves += count,
+    }
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M> {
+  /// Returns the counts of structural operations performed on the list.
+  ///
+  /// This method is only available when the `metrics` feature is enabled.
+  #[inline]
+  pub fn op_stats(&self) -> OpStats {
+    self.stats
+  }
+
+  /// Resets the counts of structural operations to zero.
+  ///
+  /// This method is only available when the `metrics` feature is enabled.
+  #[inline]
+  pub fn reset_op_stats(&mut self) {
+    self.stats = OpStats::default();
+  }
+}
@@ -24,8 +24,51 @@ pub trait Node {
 }
 
 /// Implements [`Node`] trait for the specific structure.
+///
+/// If the `accessors` flag is given, public methods `prev_key` and
+/// `next_key` are also generated for reading the link fields without
+/// importing the [`Node`] trait.
+///
+/// # Examples
+///
+/// ```
+/// use key_node_list::impl_node;
+///
+/// struct MyNode {
+///   prev: Option<i32>,
+///   next: Option<i32>,
+/// }
+///
+/// impl_node!(MyNode { Key = i32, prev = prev, next = next, accessors });
+///
+/// let node = MyNode { prev: Some(1), next: None };
+/// assert_eq!(node.prev_key(), Some(&1));
+/// assert_eq!(node.next_key(), None);
+/// ```
 #[macro_export]
 macro_rules! impl_node {
+  (
+    $node:ident$(<$($g:tt),* $(,)?>)?
+    { Key = $key:ty, prev = $prev:ident, next = $next:ident, accessors $(,)? }
+  ) => {
+    $crate::impl_node!($node$(<$($g),*>)? { Key = $key, prev = $prev, next = $next });
+
+    impl$(<$($g),*>)? $node$(<$($g),*>)? {
+      /// Returns a reference to the previous key of the current node,
+      /// or returns `None` if the current node is the first node in the list.
+      #[inline]
+      pub fn prev_key(&self) -> Option<&$key> {
+        self.$prev.as_ref()
+      }
+
+      /// Returns a reference to the next key of the current node,
+      /// or returns `None` if the current node is the last node in the list.
+      #[inline]
+      pub fn next_key(&self) -> Option<&$key> {
+        self.$next.as_ref()
+      }
+    }
+  };
   (
     $node:ident$(<$($g:tt),* $(,)?>)?
     { Key = $key:ty, prev = $prev:ident, next = $next:ident $(,)? }
@@ -0,0 +1,102 @@
+use crate::list::KeyNodeList;
+use crate::map::{IndexKey, KeyIndex, Map, MapLookup};
+use crate::node::Node;
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+/// A comparable handle that reflects the position of a key-node pair
+/// in a [`KeyNodeList`].
+///
+/// Tokens are created by [`OrderIndex::order_key`] rather than by the
+/// list itself, since the positions are computed once by
+/// [`KeyNodeList::reindex`] and then shared by all queries. A token is
+/// only valid until the next structural mutation of the list it came
+/// from, comparing tokens from different snapshots of the list gives
+/// meaningless results. Use [`OrderIndex::is_stale`] to check whether
+/// the tokens of an index are still valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderToken(usize);
+
+/// A snapshot of the positions of all key-node pairs in a [`KeyNodeList`],
+/// created by [`KeyNodeList::reindex`].
+///
+/// `OrderIndex` stores clones of the keys instead of borrowing the list,
+/// so the list can be mutated while the index is kept. Once the list is
+/// structurally mutated, such as pairs being pushed, removed or moved, the
+/// index becomes stale, and all its queries return `None`. Mutating nodes
+/// in place does not make the index stale.
+///
+/// An index must only be queried with the list it was created from, or
+/// with clones of that list.
+pub struct OrderIndex<K> {
+  generation: usize,
+  indices: KeyIndex<K, usize>,
+}
+
+impl<K> OrderIndex<K>
+where
+  K: IndexKey,
+{
+  /// Returns `true` if `list` has been structurally mutated since the
+  /// index was created.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn is_stale<N, M>(&self, list: &KeyNodeList<K, N, M>) -> bool {
+    list.generation != self.generation
+  }
+
+  /// Returns the order token of the given key in `list`, or `None` if the
+  /// key does not exist or the index is stale.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn order_key<Q, N, M>(&self, list: &KeyNodeList<K, N, M>, key: &Q) -> Option<OrderToken>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: Map<K, N> + MapLookup<K, N, Q>,
+  {
+    if self.is_stale(list) {
+      return None;
+    }
+    let (k, _) = list.get_key_node(key)?;
+    self.indices.get::<K>(k).map(|i| OrderToken(*i))
+  }
+
+  /// Returns `true` if the pair with key `a` precedes the pair with key `b`
+  /// in `list`, or `None` if any of the keys does not exist or the index is
+  /// stale.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  pub fn is_before<Q, N, M>(&self, list: &KeyNodeList<K, N, M>, a: &Q, b: &Q) -> Option<bool>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: Map<K, N> + MapLookup<K, N, Q>,
+  {
+    Some(self.order_key(list, a)? < self.order_key(list, b)?)
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: IndexKey + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Assigns monotonically increasing indices to all key-node pairs in
+  /// list order, and returns an [`OrderIndex`] for comparing the relative
+  /// positions of pairs.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  pub fn reindex(&self) -> OrderIndex<K> {
+    OrderIndex {
+      generation: self.generation,
+      indices: self
+        .keys()
+        .enumerate()
+        .map(|(i, k)| (k.clone(), i))
+        .collect(),
+    }
+  }
+}
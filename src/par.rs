@@ -0,0 +1,35 @@
+use crate::list::KeyNodeList;
+use crate::map::Map;
+use crate::node::Node;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use rayon::prelude::*;
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Sync,
+  N: Node<Key = K> + Sync,
+  M: Map<K, N>,
+{
+  /// Returns a parallel iterator over all keys and nodes.
+  /// The iterator element type is `(&'a K, &'a N)`.
+  ///
+  /// The list order is collected sequentially in *O*(n) time before the
+  /// parallel iteration, and is preserved by the returned indexed iterator.
+  ///
+  /// This method is only available when the `rayon` feature is enabled.
+  pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = (&K, &N)> {
+    self.iter().collect::<Vec<_>>().into_par_iter()
+  }
+
+  /// Returns a parallel iterator over all nodes.
+  /// The iterator element type is `&'a N`.
+  ///
+  /// The list order is collected sequentially in *O*(n) time before the
+  /// parallel iteration, and is preserved by the returned indexed iterator.
+  ///
+  /// This method is only available when the `rayon` feature is enabled.
+  pub fn par_nodes(&self) -> impl IndexedParallelIterator<Item = &N> {
+    self.nodes().collect::<Vec<_>>().into_par_iter()
+  }
+}
@@ -0,0 +1,394 @@
+use crate::cursor::Cursor;
+use crate::iter::Iter;
+use crate::list::KeyNodeList;
+use crate::map::{Map, MapLookup};
+use crate::node::Node;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::Hash;
+
+/// A read-only view of a [`KeyNodeList`] that presents the list as if it
+/// were reversed, created by [`KeyNodeList::reversed_view`].
+///
+/// The front of the view is the back of the list, and iterating over the
+/// view yields key-node pairs from the back of the list to the front.
+/// The underlying list is not modified.
+pub struct ReversedView<'a, K, N, M> {
+  list: &'a KeyNodeList<K, N, M>,
+}
+
+impl<'a, K, N, M> Clone for ReversedView<'a, K, N, M> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a, K, N, M> Copy for ReversedView<'a, K, N, M> {}
+
+impl<K, N, M> KeyNodeList<K, N, M> {
+  /// Returns a view of the list that presents it as if it were reversed,
+  /// without modifying the list.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn reversed_view(&self) -> ReversedView<'_, K, N, M> {
+    ReversedView { list: self }
+  }
+}
+
+impl<'a, K, N, M> ReversedView<'a, K, N, M>
+where
+  M: Map<K, N>,
+{
+  /// Returns the underlying list.
+  #[inline]
+  pub fn list(&self) -> &'a KeyNodeList<K, N, M> {
+    self.list
+  }
+
+  /// Returns a reference to the front key of the view, which is the back
+  /// key of the list, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn front_key(&self) -> Option<&'a K> {
+    self.list.back_key()
+  }
+
+  /// Returns a reference to the back key of the view, which is the front
+  /// key of the list, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn back_key(&self) -> Option<&'a K> {
+    self.list.front_key()
+  }
+
+  /// Returns the number of key-node pairs in the list.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.list.len()
+  }
+
+  /// Returns `true` if the list contains no key-node pairs.
+  ///
+  /// This operation should compute in *O*(1) time.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.list.is_empty()
+  }
+
+  /// Returns an iterator over all keys and nodes of the view, from the
+  /// back of the list to the front.
+  /// The iterator element type is `(&'a K, &'a N)`.
+  #[inline]
+  pub fn iter(&self) -> RevIter<'a, K, N, M> {
+    RevIter {
+      iter: self.list.iter(),
+    }
+  }
+
+  /// Returns an iterator over all keys of the view, from the back of the
+  /// list to the front.
+  /// The iterator element type is `&'a K`.
+  #[inline]
+  pub fn keys(&self) -> impl Iterator<Item = &'a K>
+  where
+    K: Hash + Eq + 'a,
+    N: Node<Key = K> + 'a,
+  {
+    self.iter().map(|(k, _)| k)
+  }
+
+  /// Returns an iterator over all nodes of the view, from the back of the
+  /// list to the front.
+  /// The iterator element type is `&'a N`.
+  #[inline]
+  pub fn nodes(&self) -> impl Iterator<Item = &'a N>
+  where
+    K: Hash + Eq + 'a,
+    N: Node<Key = K> + 'a,
+  {
+    self.iter().map(|(_, n)| n)
+  }
+}
+
+impl<'a, K, N, M> ReversedView<'a, K, N, M>
+where
+  K: Hash + Eq,
+  M: Map<K, N>,
+{
+  /// Returns `true` if the list contains a node for the specified key.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.list.contains_key(key)
+  }
+
+  /// Returns a reference to the node corresponding to the key,
+  /// or `None` if key does not exist.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn node<Q>(&self, key: &Q) -> Option<&'a N>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+    M: MapLookup<K, N, Q>,
+  {
+    self.list.node(key)
+  }
+
+  /// Returns a reference to the front node of the view, which is the back
+  /// node of the list, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn front_node(&self) -> Option<&'a N> {
+    self.list.back_node()
+  }
+
+  /// Returns a reference to the back node of the view, which is the front
+  /// node of the list, or `None` if the list is empty.
+  ///
+  /// This operation should compute in *O*(1) time on average.
+  #[inline]
+  pub fn back_node(&self) -> Option<&'a N> {
+    self.list.front_node()
+  }
+
+  /// Provides a reversed cursor at the specific key.
+  ///
+  /// The cursor is pointing to the null pair if the key does not exist.
+  #[inline]
+  pub fn cursor(&self, key: K) -> ReversedCursor<'a, K, N, M> {
+    ReversedCursor {
+      cursor: self.list.cursor(key),
+    }
+  }
+}
+
+impl<'a, K, N, M> ReversedView<'a, K, N, M>
+where
+  K: Hash + Eq + Clone,
+  M: Map<K, N>,
+{
+  /// Provides a reversed cursor at the front key-node pair of the view,
+  /// which is the back pair of the list.
+  ///
+  /// The cursor is pointing to the null pair if the list is empty.
+  #[inline]
+  pub fn cursor_front(&self) -> ReversedCursor<'a, K, N, M> {
+    ReversedCursor {
+      cursor: self.list.cursor_back(),
+    }
+  }
+
+  /// Provides a reversed cursor at the back key-node pair of the view,
+  /// which is the front pair of the list.
+  ///
+  /// The cursor is pointing to the null pair if the list is empty.
+  #[inline]
+  pub fn cursor_back(&self) -> ReversedCursor<'a, K, N, M> {
+    ReversedCursor {
+      cursor: self.list.cursor_front(),
+    }
+  }
+}
+
+impl<'a, K, N, M> IntoIterator for &ReversedView<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  type Item = (&'a K, &'a N);
+  type IntoIter = RevIter<'a, K, N, M>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, N, M> IntoIterator for ReversedView<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  type Item = (&'a K, &'a N);
+  type IntoIter = RevIter<'a, K, N, M>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, N, M> fmt::Debug for ReversedView<'a, K, N, M>
+where
+  K: Hash + Eq + fmt::Debug,
+  N: Node<Key = K> + fmt::Debug,
+  M: Map<K, N>,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_list().entries(self).finish()
+  }
+}
+
+/// An iterator over the key-node pairs of a [`KeyNodeList`],
+/// from the back of the list to the front.
+///
+/// This is an [`Iter`] that runs in the opposite direction.
+pub struct RevIter<'a, K, N, M> {
+  iter: Iter<'a, K, N, M>,
+}
+
+impl<'a, K, N, M> Clone for RevIter<'a, K, N, M> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      iter: self.iter.clone(),
+    }
+  }
+}
+
+impl<'a, K, N, M> Iterator for RevIter<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  type Item = (&'a K, &'a N);
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next_back()
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'a, K, N, M> DoubleEndedIterator for RevIter<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+}
+
+impl<'a, K, N, M> ExactSizeIterator for RevIter<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+}
+
+/// A cursor over a [`ReversedView`], which moves in the reversed direction
+/// of the underlying [`KeyNodeList`].
+#[derive(Clone)]
+pub struct ReversedCursor<'a, K, N, M> {
+  cursor: Cursor<'a, K, N, M>,
+}
+
+impl<'a, K, N, M> ReversedCursor<'a, K, N, M> {
+  /// Checks if the cursor is currently pointing to the null pair.
+  #[inline]
+  pub fn is_null(&self) -> bool {
+    self.cursor.is_null()
+  }
+
+  /// Returns a reference to the key that the cursor is currently pointing to.
+  ///
+  /// Returns `None` if the cursor is currently pointing to the null pair.
+  #[inline]
+  pub fn key(&self) -> Option<&K> {
+    self.cursor.key()
+  }
+}
+
+impl<'a, K, N, M> ReversedCursor<'a, K, N, M>
+where
+  K: Hash + Eq,
+  M: Map<K, N>,
+{
+  /// Returns a reference to the node that the cursor is currently pointing to.
+  ///
+  /// Returns `None` if the cursor is currently pointing to the null pair.
+  #[inline]
+  pub fn node(&self) -> Option<&N> {
+    self.cursor.node()
+  }
+}
+
+impl<'a, K, N, M> ReversedCursor<'a, K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Returns a reference to the next key in the view, which is the
+  /// previous key in the list.
+  #[inline]
+  pub fn next_key(&self) -> Option<&K> {
+    self.cursor.prev_key()
+  }
+
+  /// Returns a reference to the previous key in the view, which is the
+  /// next key in the list.
+  #[inline]
+  pub fn prev_key(&self) -> Option<&K> {
+    self.cursor.next_key()
+  }
+
+  /// Returns a reference to the next node in the view, which is the
+  /// previous node in the list.
+  #[inline]
+  pub fn next_node(&self) -> Option<&N> {
+    self.cursor.prev_node()
+  }
+
+  /// Returns a reference to the previous node in the view, which is the
+  /// next node in the list.
+  #[inline]
+  pub fn prev_node(&self) -> Option<&N> {
+    self.cursor.next_node()
+  }
+}
+
+impl<'a, K, N, M> ReversedCursor<'a, K, N, M>
+where
+  K: Hash + Eq + Clone,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Moves the cursor to the next key-node pair of the view, which is the
+  /// previous pair of the list.
+  #[inline]
+  pub fn move_next(&mut self) {
+    self.cursor.move_prev()
+  }
+
+  /// Moves the cursor to the previous key-node pair of the view, which is
+  /// the next pair of the list.
+  #[inline]
+  pub fn move_prev(&mut self) {
+    self.cursor.move_next()
+  }
+}
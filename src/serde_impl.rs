@@ -0,0 +1,156 @@
+use crate::list::KeyNodeList;
+use crate::map::Map;
+use crate::node::{Node, ValueNode};
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// The key order of a [`KeyNodeList`], created by
+/// [`KeyNodeList::key_order`].
+///
+/// `KeyOrder` serializes the keys of the list as a sequence in list order,
+/// without the nodes. The sequence can be deserialized as a `Vec<K>`, and
+/// combined with a separately deserialized map by
+/// [`KeyNodeList::from_map_with_order`].
+///
+/// This structure is only available when the `serde` feature is enabled.
+#[derive(Debug, Clone)]
+pub struct KeyOrder<'a, K> {
+  keys: Vec<&'a K>,
+}
+
+impl<'a, K> KeyOrder<'a, K> {
+  /// Returns the keys in list order.
+  #[inline]
+  pub fn keys(&self) -> &[&'a K] {
+    &self.keys
+  }
+}
+
+impl<'a, K> Serialize for KeyOrder<'a, K>
+where
+  K: Serialize,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_seq(&self.keys)
+  }
+}
+
+impl<K, N, M> KeyNodeList<K, N, M>
+where
+  K: Hash + Eq,
+  N: Node<Key = K>,
+  M: Map<K, N>,
+{
+  /// Returns the key order of the list, which can be serialized separately
+  /// from the nodes.
+  ///
+  /// This method is only available when the `serde` feature is enabled.
+  ///
+  /// This operation should compute in *O*(n) time on average.
+  #[inline]
+  pub fn key_order(&self) -> KeyOrder<'_, K> {
+    KeyOrder {
+      keys: self.keys().collect(),
+    }
+  }
+}
+
+/// Serializes the list as a sequence of key-node pairs in list order.
+impl<K, N, M> Serialize for KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Serialize,
+  N: Node<Key = K> + Serialize,
+  M: Map<K, N>,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_seq(self.iter())
+  }
+}
+
+/// Deserializes the list from a sequence of key-node pairs, pushing each
+/// pair to the back of the list, so the order round-trips.
+///
+/// Duplicate keys are rejected with an error.
+impl<'de, K, N, M> Deserialize<'de> for KeyNodeList<K, N, M>
+where
+  K: Hash + Eq + Clone + Deserialize<'de>,
+  N: Node<Key = K> + Deserialize<'de>,
+  M: Map<K, N> + Default,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_seq(ListVisitor(PhantomData))
+  }
+}
+
+/// Visitor for deserializing a [`KeyNodeList`].
+struct ListVisitor<K, N, M>(PhantomData<KeyNodeList<K, N, M>>);
+
+impl<'de, K, N, M> Visitor<'de> for ListVisitor<K, N, M>
+where
+  K: Hash + Eq + Clone + Deserialize<'de>,
+  N: Node<Key = K> + Deserialize<'de>,
+  M: Map<K, N> + Default,
+{
+  type Value = KeyNodeList<K, N, M>;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("a sequence of key-node pairs")
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    let mut list = KeyNodeList::new();
+    let mut index = 0;
+    while let Some((key, node)) = seq.next_element::<(K, N)>()? {
+      if list.push_back(key, node).is_err() {
+        return Err(de::Error::custom(format_args!(
+          "duplicate key at index {}",
+          index
+        )));
+      }
+      index += 1;
+    }
+    Ok(list)
+  }
+}
+
+/// Serializes only the value of the node, the links are skipped.
+impl<K, V> Serialize for ValueNode<K, V>
+where
+  V: Serialize,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    self.value().serialize(serializer)
+  }
+}
+
+/// Deserializes the value of the node, the links are left empty.
+impl<'de, K, V> Deserialize<'de> for ValueNode<K, V>
+where
+  V: Deserialize<'de>,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    V::deserialize(deserializer).map(ValueNode::new)
+  }
+}